@@ -0,0 +1,640 @@
+//! A module for testing the association between two [`Fraction`]s.
+//!
+//! The fractions form a 2×2 contingency table, where the first fraction is the first row
+//! and the second fraction is the second row of the table:
+//!
+//! | | Feature present | Feature excluded |
+//! |---|---|---|
+//! | Group 1 | `x.n()` | `x.m() - x.n()` |
+//! | Group 2 | `y.n()` | `y.m() - y.n()` |
+//!
+//! For instance, the rows can correspond to the carriers of missense and truncating variants
+//! and the columns to the presence and exclusion of a phenotypic feature.
+//!
+//! ## Examples
+//!
+//! ```
+//! use phenotypes::Fraction;
+//! use phenotypes::association::{Alternative, chi_square_test, fisher_exact_test};
+//!
+//! let missense = Fraction::try_from((8u32, 10)).unwrap();
+//! let truncating = Fraction::try_from((1u32, 6)).unwrap();
+//!
+//! let fisher = fisher_exact_test(&missense, &truncating, Alternative::TwoSided);
+//! assert!((fisher.p_value() - 0.03496503).abs() < 1e-8);
+//! assert_eq!(fisher.odds_ratio(), 20.);
+//!
+//! let ci = fisher.odds_ratio_interval(0.95);
+//! assert!(ci.contains(fisher.odds_ratio()));
+//!
+//! let chi2 = chi_square_test(&missense, &truncating).unwrap();
+//! assert!((chi2.statistic() - 3.809524).abs() < 1e-6);
+//! assert!((chi2.p_value() - 0.050962).abs() < 1e-6);
+//! ```
+//!
+//! Testing many features across two groups of items requires a [`MultipleTestingCorrection`]
+//! and it is best done with an [`AssociationScan`].
+use std::collections::BTreeSet;
+
+use ontolius::{Identified, TermId};
+
+use crate::cohort::FeatureFrequencies;
+use crate::interval::critical_value;
+use crate::stats::{gamma_q, ln_choose};
+use crate::{ConfidenceInterval, Count, Fraction, ObservableFeatures};
+
+/// The alternative hypothesis of a test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Alternative {
+    /// The odds ratio is not equal to one.
+    #[default]
+    TwoSided,
+    /// The odds ratio is less than one.
+    Less,
+    /// The odds ratio is greater than one.
+    Greater,
+}
+
+/// The cells of a 2×2 contingency table built from two fractions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Table {
+    a: f64,
+    b: f64,
+    c: f64,
+    d: f64,
+}
+
+impl Table {
+    fn new<T>(x: &Fraction<T>, y: &Fraction<T>) -> Self
+    where
+        T: Count,
+    {
+        let (xn, xm): (f64, f64) = (x.n().to_f64(), x.m().to_f64());
+        let (yn, ym): (f64, f64) = (y.n().to_f64(), y.m().to_f64());
+        Self {
+            a: xn,
+            b: xm - xn,
+            c: yn,
+            d: ym - yn,
+        }
+    }
+
+    fn total(&self) -> f64 {
+        self.a + self.b + self.c + self.d
+    }
+
+    fn odds_ratio(&self) -> f64 {
+        // `0 / 0` is NaN and `x / 0` is infinite.
+        (self.a * self.d) / (self.b * self.c)
+    }
+}
+
+/// The result of [`fisher_exact_test`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FisherExactResult {
+    p_value: f64,
+    table: Table,
+}
+
+impl FisherExactResult {
+    /// Get the p-value of the test.
+    pub fn p_value(&self) -> f64 {
+        self.p_value
+    }
+
+    /// Get the sample odds ratio `(a * d) / (b * c)`.
+    ///
+    /// The odds ratio is infinite if `b * c` is zero,
+    /// and it is NaN if both `a * d` and `b * c` are zero.
+    pub fn odds_ratio(&self) -> f64 {
+        self.table.odds_ratio()
+    }
+
+    /// Get the confidence interval of the odds ratio using the Woolf logit method.
+    ///
+    /// The Haldane–Anscombe correction, adding `0.5` to each cell,
+    /// is applied if any cell of the table is zero.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the `confidence_level` is not in the open interval `(0, 1)`.
+    pub fn odds_ratio_interval(&self, confidence_level: f64) -> ConfidenceInterval {
+        let Table { a, b, c, d } = self.table;
+        let (a, b, c, d) = if a * b * c * d == 0. {
+            (a + 0.5, b + 0.5, c + 0.5, d + 0.5)
+        } else {
+            (a, b, c, d)
+        };
+        let ln_or = (a * d / (b * c)).ln();
+        let se = (1. / a + 1. / b + 1. / c + 1. / d).sqrt();
+        let z = critical_value(confidence_level);
+
+        ConfidenceInterval::new(
+            (ln_or - z * se).exp(),
+            (ln_or + z * se).exp(),
+            confidence_level,
+        )
+    }
+}
+
+/// Run Fisher's exact test on the 2×2 table formed by the fractions `x` and `y`.
+///
+/// The probabilities of the tables are computed in log space,
+/// which keeps the test numerically stable for denominators in the thousands.
+/// The two-sided p-value is the sum of the probabilities of all tables
+/// that are at most as probable as the observed table.
+///
+/// ```
+/// use phenotypes::Fraction;
+/// use phenotypes::association::{Alternative, fisher_exact_test};
+///
+/// let x = Fraction::try_from((1200u32, 3000)).unwrap();
+/// let y = Fraction::try_from((1000u32, 3000)).unwrap();
+///
+/// let greater = fisher_exact_test(&x, &y, Alternative::Greater);
+/// assert!((greater.p_value() - 4.807162e-8).abs() < 1e-13);
+///
+/// let less = fisher_exact_test(&x, &y, Alternative::Less);
+/// assert!(less.p_value() > 0.99);
+/// ```
+pub fn fisher_exact_test<T>(
+    x: &Fraction<T>,
+    y: &Fraction<T>,
+    alternative: Alternative,
+) -> FisherExactResult
+where
+    T: Count,
+{
+    let table = Table::new(x, y);
+    FisherExactResult {
+        p_value: fisher_p_value(&table, alternative),
+        table,
+    }
+}
+
+fn fisher_p_value(table: &Table, alternative: Alternative) -> f64 {
+    let row1 = table.a + table.b;
+    let row2 = table.c + table.d;
+    let col1 = table.a + table.c;
+    let n = table.total();
+
+    let lo = (col1 - row2).max(0.) as u64;
+    let hi = col1.min(row1) as u64;
+    let observed = table.a as u64;
+    let ln_denominator = ln_choose(n, col1);
+    let ln_p = |k: u64| {
+        let k = k as f64;
+        ln_choose(row1, k) + ln_choose(row2, col1 - k) - ln_denominator
+    };
+
+    let p: f64 = match alternative {
+        Alternative::Less => (lo..=observed).map(|k| ln_p(k).exp()).sum(),
+        Alternative::Greater => (observed..=hi).map(|k| ln_p(k).exp()).sum(),
+        Alternative::TwoSided => {
+            // The relative tolerance guards against floating point ties.
+            let threshold = ln_p(observed) + 1e-7;
+            (lo..=hi)
+                .map(ln_p)
+                .filter(|&lp| lp <= threshold)
+                .map(f64::exp)
+                .sum()
+        }
+    };
+    p.min(1.)
+}
+
+/// The result of [`chi_square_test`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChiSquareResult {
+    statistic: f64,
+    p_value: f64,
+}
+
+impl ChiSquareResult {
+    /// Get the chi-square statistic.
+    pub fn statistic(&self) -> f64 {
+        self.statistic
+    }
+
+    /// Get the p-value of the test.
+    pub fn p_value(&self) -> f64 {
+        self.p_value
+    }
+}
+
+/// Run the chi-square test of independence with Yates' continuity correction
+/// on the 2×2 table formed by the fractions `x` and `y`.
+///
+/// Returns `None` if any row or column of the table sums up to zero.
+pub fn chi_square_test<T>(x: &Fraction<T>, y: &Fraction<T>) -> Option<ChiSquareResult>
+where
+    T: Count,
+{
+    let Table { a, b, c, d } = Table::new(x, y);
+    let n = a + b + c + d;
+    let margins = (a + b) * (c + d) * (a + c) * (b + d);
+    if margins == 0. {
+        return None;
+    }
+
+    let diff = ((a * d - b * c).abs() - n / 2.).max(0.);
+    let statistic = n * diff * diff / margins;
+
+    Some(ChiSquareResult {
+        statistic,
+        p_value: gamma_q(0.5, statistic / 2.),
+    })
+}
+
+/// A procedure for correcting the p-values for multiple testing.
+///
+/// ```
+/// use phenotypes::association::MultipleTestingCorrection;
+///
+/// let p_values = [0.01, 0.04, 0.03, 0.2];
+///
+/// let bonferroni = MultipleTestingCorrection::Bonferroni.adjust(&p_values);
+/// assert_eq!(bonferroni, [0.04, 0.16, 0.12, 0.8]);
+///
+/// let holm = MultipleTestingCorrection::Holm.adjust(&p_values);
+/// assert_eq!(holm, [0.04, 0.09, 0.09, 0.2]);
+///
+/// let bh = MultipleTestingCorrection::BenjaminiHochberg.adjust(&p_values);
+/// let expected = [0.04, 0.16 / 3., 0.16 / 3., 0.2];
+/// assert!(bh.iter().zip(expected).all(|(p, e)| (p - e).abs() < 1e-12));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MultipleTestingCorrection {
+    /// Bonferroni correction of the family-wise error rate.
+    Bonferroni,
+    /// Holm–Bonferroni step-down correction of the family-wise error rate.
+    Holm,
+    /// Benjamini–Hochberg correction of the false discovery rate.
+    #[default]
+    BenjaminiHochberg,
+}
+
+impl MultipleTestingCorrection {
+    /// Get the corrected p-values in the order of the input `p_values`.
+    pub fn adjust(&self, p_values: &[f64]) -> Vec<f64> {
+        let m = p_values.len() as f64;
+        let mut order: Vec<_> = (0..p_values.len()).collect();
+        order.sort_by(|&i, &j| p_values[i].total_cmp(&p_values[j]));
+
+        let mut adjusted = vec![0.; p_values.len()];
+        match self {
+            MultipleTestingCorrection::Bonferroni => {
+                for (adj, p) in adjusted.iter_mut().zip(p_values) {
+                    *adj = (p * m).min(1.);
+                }
+            }
+            MultipleTestingCorrection::Holm => {
+                let mut running_max: f64 = 0.;
+                for (rank, &i) in order.iter().enumerate() {
+                    running_max = running_max.max(((m - rank as f64) * p_values[i]).min(1.));
+                    adjusted[i] = running_max;
+                }
+            }
+            MultipleTestingCorrection::BenjaminiHochberg => {
+                let mut running_min: f64 = 1.;
+                for (rank, &i) in order.iter().enumerate().rev() {
+                    running_min = running_min.min(m / (rank + 1) as f64 * p_values[i]);
+                    adjusted[i] = running_min;
+                }
+            }
+        }
+        adjusted
+    }
+}
+
+/// The reason for not testing a feature in an [`AssociationScan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FilterReason {
+    /// The feature was not assessed in one of the groups.
+    NotAssessed,
+    /// The feature is present in all assessed items or excluded in all assessed items.
+    Uninformative,
+    /// The smallest p-value attainable with the counts of the feature is greater than `alpha`.
+    CannotReachSignificance,
+}
+
+/// A feature that was not tested in an [`AssociationScan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FilteredFeature {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
+    term_id: TermId,
+    reason: FilterReason,
+}
+
+impl FilteredFeature {
+    /// Get the ID of the feature.
+    pub fn term_id(&self) -> &TermId {
+        &self.term_id
+    }
+
+    /// Get the reason for not testing the feature.
+    pub fn reason(&self) -> FilterReason {
+        self.reason
+    }
+}
+
+/// The test result of a feature in an [`AssociationScan`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScanRow {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
+    term_id: TermId,
+    first: Fraction,
+    second: Fraction,
+    test: FisherExactResult,
+    corrected_p_value: f64,
+}
+
+impl ScanRow {
+    /// Get the ID of the feature.
+    pub fn term_id(&self) -> &TermId {
+        &self.term_id
+    }
+
+    /// Get the fraction of the feature in the first group.
+    pub fn first(&self) -> &Fraction {
+        &self.first
+    }
+
+    /// Get the fraction of the feature in the second group.
+    pub fn second(&self) -> &Fraction {
+        &self.second
+    }
+
+    /// Get the result of the Fisher's exact test.
+    pub fn test(&self) -> &FisherExactResult {
+        &self.test
+    }
+
+    /// Get the nominal p-value.
+    pub fn p_value(&self) -> f64 {
+        self.test.p_value()
+    }
+
+    /// Get the p-value corrected for multiple testing.
+    pub fn corrected_p_value(&self) -> f64 {
+        self.corrected_p_value
+    }
+}
+
+/// The results of an [`AssociationScan`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScanResults {
+    rows: Vec<ScanRow>,
+    filtered: Vec<FilteredFeature>,
+}
+
+impl ScanResults {
+    /// Get the results of the tested features.
+    pub fn rows(&self) -> &[ScanRow] {
+        &self.rows
+    }
+
+    /// Get the features that were not tested.
+    pub fn filtered(&self) -> &[FilteredFeature] {
+        &self.filtered
+    }
+
+    /// Sort the rows by the corrected p-value, starting from the most significant feature.
+    pub fn sort_by_corrected_p_value(&mut self) {
+        self.rows.sort_by(|a, b| {
+            a.corrected_p_value
+                .total_cmp(&b.corrected_p_value)
+                .then_with(|| a.term_id.cmp(&b.term_id))
+        });
+    }
+
+    /// Sort the rows by the term ID.
+    pub fn sort_by_term_id(&mut self) {
+        self.rows.sort_by(|a, b| a.term_id.cmp(&b.term_id));
+    }
+
+    /// Sort the rows using a custom comparator.
+    pub fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&ScanRow, &ScanRow) -> std::cmp::Ordering,
+    {
+        self.rows.sort_by(compare);
+    }
+}
+
+/// A scan for the features associated with one of two groups of items,
+/// e.g. the carriers of missense vs. truncating variants.
+///
+/// The scan builds the [`Fraction`] of each feature in both groups,
+/// filters out the features that cannot be associated with the groups,
+/// tests the remaining features with Fisher's exact test,
+/// and corrects the p-values for multiple testing.
+///
+/// A feature is filtered out if:
+///
+/// * it was not assessed in one of the groups,
+/// * it is present in all or excluded in all assessed items of both groups, or
+/// * the smallest p-value attainable with its counts under the alternative hypothesis
+///   is greater than `alpha`.
+///
+/// Filtering out the features that cannot reach significance reduces
+/// the burden of the multiple testing correction.
+///
+/// ## Examples
+///
+/// ```
+/// use ontolius::TermId;
+/// use phenotypes::Fraction;
+/// use phenotypes::association::{AssociationScan, FilterReason, MultipleTestingCorrection};
+/// use phenotypes::simple::SimplePhenotypicFeature;
+///
+/// let seizure: TermId = "HP:0001250".parse().unwrap();
+/// let polydactyly: TermId = "HP:0010442".parse().unwrap();
+///
+/// let subject = |seizure_present, polydactyly_present| vec![
+///     SimplePhenotypicFeature::new(
+///         "HP:0001250".parse().unwrap(),
+///         Fraction::try_from((seizure_present, 1)).unwrap(),
+///     ),
+///     SimplePhenotypicFeature::new(
+///         "HP:0010442".parse().unwrap(),
+///         Fraction::try_from((polydactyly_present, 1)).unwrap(),
+///     ),
+/// ];
+///
+/// let missense: Vec<_> = (0..10).map(|i| subject(u32::from(i < 9), 1)).collect();
+/// let truncating: Vec<_> = (0..10).map(|i| subject(u32::from(i < 2), 1)).collect();
+///
+/// let mut results = AssociationScan::default()
+///     .correction(MultipleTestingCorrection::Bonferroni)
+///     .run(&missense, &truncating);
+/// results.sort_by_corrected_p_value();
+///
+/// let row = &results.rows()[0];
+/// assert_eq!(row.term_id(), &seizure);
+/// assert_eq!(row.first(), &Fraction::try_from((9, 10)).unwrap());
+/// assert!(row.corrected_p_value() < 0.01);
+///
+/// // Polydactyly is present in all subjects.
+/// let filtered = &results.filtered()[0];
+/// assert_eq!(filtered.term_id(), &polydactyly);
+/// assert_eq!(filtered.reason(), FilterReason::Uninformative);
+/// ```
+///
+/// A one-sided test can reach a smaller p-value than the two-sided test.
+/// The feature present in 3 of 3 and in 0 of 3 subjects has the smallest attainable
+/// two-sided p-value of `0.1` but the one-sided p-value of `0.05`:
+///
+/// ```
+/// use phenotypes::Fraction;
+/// use phenotypes::association::{Alternative, AssociationScan, FilterReason};
+/// use phenotypes::simple::SimplePhenotypicFeature;
+///
+/// let subject = |present| vec![SimplePhenotypicFeature::new(
+///     "HP:0001250".parse().unwrap(),
+///     Fraction::try_from((present, 1u32)).unwrap(),
+/// )];
+/// let first: Vec<_> = (0..3).map(|_| subject(1)).collect();
+/// let second: Vec<_> = (0..3).map(|_| subject(0)).collect();
+///
+/// let scan = AssociationScan::default().alpha(0.06);
+///
+/// let results = scan.run(&first, &second);
+/// assert_eq!(results.filtered()[0].reason(), FilterReason::CannotReachSignificance);
+///
+/// let results = scan.alternative(Alternative::Greater).run(&first, &second);
+/// assert!(results.filtered().is_empty());
+/// assert!((results.rows()[0].p_value() - 0.05).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AssociationScan {
+    alpha: f64,
+    correction: MultipleTestingCorrection,
+    alternative: Alternative,
+}
+
+/// Use `alpha = 0.05`, the Benjamini–Hochberg correction and the two-sided test.
+impl Default for AssociationScan {
+    fn default() -> Self {
+        Self {
+            alpha: 0.05,
+            correction: MultipleTestingCorrection::default(),
+            alternative: Alternative::default(),
+        }
+    }
+}
+
+impl AssociationScan {
+    /// Set the significance level for filtering the features.
+    #[must_use]
+    pub fn alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Set the multiple testing correction.
+    #[must_use]
+    pub fn correction(mut self, correction: MultipleTestingCorrection) -> Self {
+        self.correction = correction;
+        self
+    }
+
+    /// Set the alternative hypothesis of the tests.
+    #[must_use]
+    pub fn alternative(mut self, alternative: Alternative) -> Self {
+        self.alternative = alternative;
+        self
+    }
+
+    /// Run the scan on the `first` and the `second` group of items.
+    pub fn run<'a, I, J, S>(&self, first: I, second: J) -> ScanResults
+    where
+        I: IntoIterator<Item = &'a S>,
+        J: IntoIterator<Item = &'a S>,
+        S: ObservableFeatures + 'a,
+        S::Feature: Identified,
+    {
+        let first = FeatureFrequencies::from_items(first);
+        let second = FeatureFrequencies::from_items(second);
+        let term_ids: BTreeSet<_> = first.iter().chain(second.iter()).map(|(t, _)| t).collect();
+        let empty = Fraction::try_from((0, 0)).expect("0/0 should be a valid fraction");
+
+        let mut rows = vec![];
+        let mut filtered = vec![];
+        for term_id in term_ids {
+            let x = first.get(term_id).unwrap_or(&empty);
+            let y = second.get(term_id).unwrap_or(&empty);
+            match self.filter(x, y) {
+                Some(reason) => filtered.push(FilteredFeature {
+                    term_id: term_id.clone(),
+                    reason,
+                }),
+                None => rows.push(ScanRow {
+                    term_id: term_id.clone(),
+                    first: x.clone(),
+                    second: y.clone(),
+                    test: fisher_exact_test(x, y, self.alternative),
+                    corrected_p_value: f64::NAN,
+                }),
+            }
+        }
+
+        let p_values: Vec<_> = rows.iter().map(ScanRow::p_value).collect();
+        for (row, corrected) in rows.iter_mut().zip(self.correction.adjust(&p_values)) {
+            row.corrected_p_value = corrected;
+        }
+
+        ScanResults { rows, filtered }
+    }
+
+    fn filter(&self, x: &Fraction, y: &Fraction) -> Option<FilterReason> {
+        let n = x.n() + y.n();
+        let m = x.m() + y.m();
+        if x.m() == 0 || y.m() == 0 {
+            Some(FilterReason::NotAssessed)
+        } else if n == 0 || n == m {
+            Some(FilterReason::Uninformative)
+        } else if min_attainable_p_value(&Table::new(x, y), self.alternative) > self.alpha {
+            Some(FilterReason::CannotReachSignificance)
+        } else {
+            None
+        }
+    }
+}
+
+/// Get the smallest Fisher's exact test p-value attainable
+/// with the row and column sums of the `table` under the `alternative`.
+///
+/// The hypergeometric distribution is unimodal,
+/// hence the smallest two-sided p-value is attained by one of the two most extreme tables.
+/// The smallest one-sided p-value is attained by the extreme table in the tested direction.
+fn min_attainable_p_value(table: &Table, alternative: Alternative) -> f64 {
+    let row1 = table.a + table.b;
+    let row2 = table.c + table.d;
+    let col1 = table.a + table.c;
+    let extreme = |a: f64| Table {
+        a,
+        b: row1 - a,
+        c: col1 - a,
+        d: row2 - col1 + a,
+    };
+    let lo = extreme((col1 - row2).max(0.));
+    let hi = extreme(col1.min(row1));
+
+    match alternative {
+        Alternative::Less => fisher_p_value(&lo, alternative),
+        Alternative::Greater => fisher_p_value(&hi, alternative),
+        Alternative::TwoSided => {
+            fisher_p_value(&lo, alternative).min(fisher_p_value(&hi, alternative))
+        }
+    }
+}
@@ -0,0 +1,326 @@
+//! A module for the Bayesian estimation of feature frequencies.
+//!
+//! The ratio of a small [`Fraction`], such as `1/2` or `0/3`, is a poor estimate
+//! of the feature frequency. The Beta-binomial model updates a [`Prior`] Beta distribution
+//! with the *n* of *m* counts, yielding a posterior [`BetaDistribution`]
+//! of the feature frequency:
+//!
+//! *Beta(α, β)* → *Beta(α + n, β + m - n)*
+//!
+//! ## Examples
+//!
+//! ```
+//! use phenotypes::{Fraction, FrequencyCategory};
+//! use phenotypes::bayes::Prior;
+//!
+//! let f = Fraction::try_from((0u32, 3)).unwrap();
+//!
+//! let posterior = f.posterior(&Prior::Uniform);
+//! assert_eq!(posterior.alpha(), 1.);
+//! assert_eq!(posterior.beta(), 4.);
+//! assert_eq!(posterior.mean(), 0.2);
+//! assert_eq!(posterior.mode(), Some(0.));
+//!
+//! let ci = posterior.credible_interval(0.95);
+//! assert!((ci.lower() - 0.0063).abs() < 1e-4);
+//! assert!((ci.upper() - 0.6024).abs() < 1e-4);
+//!
+//! // The prior knowledge that the feature is frequent pulls the estimate up.
+//! let informed = f.posterior(&Prior::Category {
+//!     category: FrequencyCategory::Frequent,
+//!     strength: 10.,
+//! });
+//! assert!(informed.mean() > posterior.mean());
+//! ```
+//!
+//! The fractions reported by several publications are combined
+//! by a [`HierarchicalPool`].
+use crate::interval::critical_value;
+use crate::stats::{beta_cdf, beta_quantile};
+use crate::{ConfidenceInterval, Count, Fraction, FrequencyCategory, PhenotypesError};
+
+/// A prior Beta distribution of a feature frequency.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Prior {
+    /// The uniform *Beta(1, 1)* prior.
+    Uniform,
+    /// The Jeffreys *Beta(1/2, 1/2)* prior.
+    Jeffreys,
+    /// A prior centered at the probability of the HPO frequency `category`.
+    ///
+    /// The `strength` is the number of pseudo-observations put on the category probability,
+    /// on top of the Jeffreys prior to keep the prior proper for the obligate
+    /// and the excluded categories:
+    /// *Beta(1/2 + s·p, 1/2 + s·(1 - p))*.
+    Category {
+        category: FrequencyCategory,
+        strength: f64,
+    },
+    /// A custom *Beta(α, β)* prior.
+    Beta { alpha: f64, beta: f64 },
+}
+
+impl Prior {
+    /// Get the prior as a [`BetaDistribution`].
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the parameters of the [`Prior::Beta`] are not positive
+    /// or if the strength of the [`Prior::Category`] is negative.
+    pub fn distribution(&self) -> BetaDistribution {
+        let (alpha, beta) = match self {
+            Prior::Uniform => (1., 1.),
+            Prior::Jeffreys => (0.5, 0.5),
+            Prior::Category { category, strength } => {
+                assert!(
+                    *strength >= 0.,
+                    "Prior strength must not be negative but was {strength}"
+                );
+                let p = category.probability();
+                (0.5 + strength * p, 0.5 + strength * (1. - p))
+            }
+            Prior::Beta { alpha, beta } => (*alpha, *beta),
+        };
+        BetaDistribution::new(alpha, beta).expect("Prior parameters must be positive")
+    }
+}
+
+/// The Beta distribution of a feature frequency.
+///
+/// ```
+/// use phenotypes::bayes::BetaDistribution;
+///
+/// let beta = BetaDistribution::new(2., 2.).unwrap();
+/// assert_eq!(beta.mean(), 0.5);
+/// assert_eq!(beta.variance(), 0.05);
+/// assert!((beta.cdf(0.5) - 0.5).abs() < 1e-12);
+///
+/// assert!(BetaDistribution::new(0., 1.).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BetaDistribution {
+    alpha: f64,
+    beta: f64,
+}
+
+impl BetaDistribution {
+    /// Create the *Beta(α, β)* distribution.
+    ///
+    /// Returns an error if `alpha` or `beta` is not a positive finite number.
+    pub fn new(alpha: f64, beta: f64) -> Result<Self, PhenotypesError> {
+        for value in [alpha, beta] {
+            if !(value > 0. && value.is_finite()) {
+                return Err(PhenotypesError::InvalidValue {
+                    value: value.to_string(),
+                    expected: "a positive finite number".to_string(),
+                });
+            }
+        }
+        Ok(Self { alpha, beta })
+    }
+
+    /// Get the α parameter.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Get the β parameter.
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    /// Get the mean of the distribution.
+    pub fn mean(&self) -> f64 {
+        self.alpha / (self.alpha + self.beta)
+    }
+
+    /// Get the mode of the distribution,
+    /// or `None` if the distribution is bimodal (both α and β are below one)
+    /// or uniform.
+    pub fn mode(&self) -> Option<f64> {
+        let (a, b) = (self.alpha, self.beta);
+        if a > 1. && b > 1. {
+            Some((a - 1.) / (a + b - 2.))
+        } else if a <= 1. && b > 1. {
+            Some(0.)
+        } else if a > 1. && b <= 1. {
+            Some(1.)
+        } else {
+            None
+        }
+    }
+
+    /// Get the variance of the distribution.
+    pub fn variance(&self) -> f64 {
+        let total = self.alpha + self.beta;
+        self.alpha * self.beta / (total * total * (total + 1.))
+    }
+
+    /// Get the cumulative probability of the frequency being at most `x`.
+    pub fn cdf(&self, x: f64) -> f64 {
+        beta_cdf(x, self.alpha, self.beta)
+    }
+
+    /// Get the frequency below which lies the probability `p`.
+    pub fn quantile(&self, p: f64) -> f64 {
+        beta_quantile(p, self.alpha, self.beta)
+    }
+
+    /// Get the equal-tailed credible interval with the `credible_level` coverage.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the `credible_level` is not in the open interval `(0, 1)`.
+    pub fn credible_interval(&self, credible_level: f64) -> ConfidenceInterval {
+        // Validates the level.
+        critical_value(credible_level);
+        let tail = (1. - credible_level) / 2.;
+        ConfidenceInterval::new(
+            self.quantile(tail),
+            self.quantile(1. - tail),
+            credible_level,
+        )
+    }
+
+    /// Update the distribution with the *n* of *m* observation.
+    pub fn update<T>(&self, fraction: &Fraction<T>) -> Self
+    where
+        T: Count,
+    {
+        let n: f64 = fraction.n().to_f64();
+        let m: f64 = fraction.m().to_f64();
+        Self {
+            alpha: self.alpha + n,
+            beta: self.beta + m - n,
+        }
+    }
+}
+
+impl<T> Fraction<T>
+where
+    T: Count,
+{
+    /// Get the posterior distribution of the frequency under the `prior`.
+    ///
+    /// The posterior of the `0/0` fraction is the prior.
+    pub fn posterior(&self, prior: &Prior) -> BetaDistribution {
+        prior.distribution().update(self)
+    }
+}
+
+/// The hierarchical (empirical Bayes) pooling of the fractions
+/// reported by several studies.
+///
+/// The study frequencies are assumed to be drawn from a population *Beta(α, β)* distribution.
+/// The population mean is estimated from the pooled counts, shrunk by the `prior`,
+/// and the population concentration *α + β* is estimated by the method of moments
+/// from the overdispersion of the study ratios. The concentration is capped
+/// at the total number of the investigated items, which corresponds to complete pooling
+/// of homogeneous studies.
+///
+/// The studies with `0/0` fractions are ignored.
+///
+/// ```
+/// use phenotypes::Fraction;
+/// use phenotypes::bayes::{HierarchicalPool, Prior};
+///
+/// let studies: Vec<Fraction> = [(1u32, 2), (0, 3), (9, 12), (0, 0)]
+///     .into_iter()
+///     .map(|pair| Fraction::try_from(pair).unwrap())
+///     .collect();
+///
+/// let pool = HierarchicalPool::fit(&studies, &Prior::Jeffreys).unwrap();
+///
+/// let population = pool.population();
+/// assert!((population.mean() - 10.5 / 18.).abs() < 1e-12);
+///
+/// // The study estimates are shrunk towards the population mean.
+/// let small = &pool.studies()[1];
+/// assert!(small.mean() > 0. && small.mean() < population.mean());
+/// assert_eq!(pool.studies().len(), 3);
+///
+/// assert!(HierarchicalPool::fit(&[Fraction::try_from((0u32, 0)).unwrap()], &Prior::Uniform).is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HierarchicalPool {
+    population: BetaDistribution,
+    studies: Vec<BetaDistribution>,
+}
+
+impl HierarchicalPool {
+    /// Fit the pool to the `fractions` of the studies.
+    ///
+    /// Returns `None` if no study investigated any item.
+    pub fn fit<'a, I, T>(fractions: I, prior: &Prior) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a Fraction<T>>,
+        T: Count + 'a,
+    {
+        let counts: Vec<(f64, f64)> = fractions
+            .into_iter()
+            .map(|f| (f.n().to_f64(), f.m().to_f64()))
+            .filter(|&(_, m)| m > 0.)
+            .collect();
+        if counts.is_empty() {
+            return None;
+        }
+
+        let prior = prior.distribution();
+        let total_n: f64 = counts.iter().map(|(n, _)| n).sum();
+        let total_m: f64 = counts.iter().map(|(_, m)| m).sum();
+        let mean = (total_n + prior.alpha) / (total_m + prior.alpha + prior.beta);
+
+        let concentration = estimate_concentration(&counts, mean).min(total_m);
+        let population = BetaDistribution::new(mean * concentration, (1. - mean) * concentration)
+            .expect("Population parameters should be positive");
+        let studies = counts
+            .iter()
+            .map(|&(n, m)| BetaDistribution {
+                alpha: population.alpha + n,
+                beta: population.beta + m - n,
+            })
+            .collect();
+
+        Some(Self {
+            population,
+            studies,
+        })
+    }
+
+    /// Get the distribution of the frequency in the population.
+    pub fn population(&self) -> &BetaDistribution {
+        &self.population
+    }
+
+    /// Get the posterior distributions of the frequency in the studies
+    /// that investigated at least one item.
+    pub fn studies(&self) -> &[BetaDistribution] {
+        &self.studies
+    }
+}
+
+/// Estimate the concentration *α + β* of the population Beta distribution
+/// from the intra-class correlation *ρ = 1 / (α + β + 1)*.
+///
+/// Returns infinity if the studies show no overdispersion.
+fn estimate_concentration(counts: &[(f64, f64)], mean: f64) -> f64 {
+    let k = counts.len() as f64;
+    let total_m: f64 = counts.iter().map(|(_, m)| m).sum();
+    let sum_sq_m: f64 = counts.iter().map(|(_, m)| m * m).sum();
+    let dispersion: f64 = counts
+        .iter()
+        .map(|&(n, m)| m * (n / m - mean).powi(2))
+        .sum();
+
+    let binomial = mean * (1. - mean);
+    let denominator = binomial * (total_m - sum_sq_m / total_m - (k - 1.));
+    let rho = (dispersion - binomial * (k - 1.)) / denominator;
+    if rho.is_finite() && rho > 0. {
+        1. / rho.min(1. - f64::EPSILON) - 1.
+    } else {
+        f64::INFINITY
+    }
+}
@@ -0,0 +1,418 @@
+//! A module for summarizing the features of a cohort of items, such as study subjects.
+//!
+//! The [`FeatureFrequencies`] counts how many items had each feature present
+//! out of the items where the feature was either present or excluded.
+//!
+//! ## Examples
+//!
+//! ```
+//! use ontolius::{Identified, TermId};
+//! use phenotypes::{Fraction, Observable, ObservationState};
+//! use phenotypes::cohort::FeatureFrequencies;
+//!
+//! struct Observation(TermId, ObservationState);
+//!
+//! impl Identified for Observation {
+//!     fn identifier(&self) -> &TermId {
+//!         &self.0
+//!     }
+//! }
+//!
+//! impl Observable for Observation {
+//!     fn observation_state(&self) -> ObservationState {
+//!         self.1
+//!     }
+//! }
+//!
+//! let polydactyly: TermId = "HP:0010442".parse().unwrap();
+//! let seizure: TermId = "HP:0001250".parse().unwrap();
+//!
+//! let cohort = vec![
+//!     vec![
+//!         Observation(polydactyly.clone(), ObservationState::Present),
+//!         Observation(seizure.clone(), ObservationState::Excluded),
+//!     ],
+//!     vec![
+//!         Observation(polydactyly.clone(), ObservationState::Excluded),
+//!         Observation(seizure.clone(), ObservationState::Unknown),
+//!     ],
+//!     vec![
+//!         Observation(polydactyly.clone(), ObservationState::Present),
+//!     ],
+//! ];
+//!
+//! let frequencies = FeatureFrequencies::from_items(&cohort);
+//!
+//! assert_eq!(frequencies.get(&polydactyly), Some(&Fraction::try_from((2, 3)).unwrap()));
+//! // The subjects who were not assessed for seizures are left out of the denominator.
+//! assert_eq!(frequencies.get(&seizure), Some(&Fraction::try_from((0, 1)).unwrap()));
+//! ```
+//!
+//! The [`SourcedFeatureFrequencies`] additionally tracks the references
+//! that contributed to the numerator and the denominator of each feature.
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use ontolius::{Identified, TermId};
+
+use crate::provenance::{Provenance, ProvenanceAware};
+use crate::simple::SimplePhenotypicFeature;
+use crate::{Fraction, ObservableFeatures, PhenotypesError};
+
+/// Per-feature [`Fraction`]s of a cohort of items.
+///
+/// The numerator of a feature is the number of items where the feature is present
+/// and the denominator is the number of items where the feature is present or excluded.
+/// The items where the feature state is unknown are left out of the denominator.
+///
+/// An item that lists a feature as both present and excluded
+/// is counted as having the feature present.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeatureFrequencies {
+    fractions: HashMap<TermId, Fraction>,
+}
+
+impl FeatureFrequencies {
+    /// Aggregate the features of the items.
+    pub fn from_items<'a, I, S>(items: I) -> Self
+    where
+        I: IntoIterator<Item = &'a S>,
+        S: ObservableFeatures + 'a,
+        S::Feature: Identified,
+    {
+        let mut frequencies = FeatureFrequencies::default();
+        for item in items {
+            frequencies.add_item(item);
+        }
+        frequencies
+    }
+
+    /// Add the features of an item to the aggregate.
+    pub fn add_item<S>(&mut self, item: &S)
+    where
+        S: ObservableFeatures,
+        S::Feature: Identified,
+    {
+        let present: HashSet<_> = item.present_features().map(|f| f.identifier()).collect();
+        let excluded: HashSet<_> = item
+            .excluded_features()
+            .map(|f| f.identifier())
+            .filter(|term_id| !present.contains(term_id))
+            .collect();
+
+        for term_id in present {
+            self.increment(term_id, 1);
+        }
+        for term_id in excluded {
+            self.increment(term_id, 0);
+        }
+    }
+
+    fn increment(&mut self, term_id: &TermId, n: u32) {
+        let delta = Fraction::try_from((n, 1)).expect("Numerator should not exceed 1");
+        match self.fractions.get_mut(term_id) {
+            Some(fraction) => *fraction = fraction.clone() + delta,
+            None => {
+                self.fractions.insert(term_id.clone(), delta);
+            }
+        }
+    }
+
+    /// Get the fraction of the feature or `None` if the feature was not assessed in any item.
+    pub fn get(&self, term_id: &TermId) -> Option<&Fraction> {
+        self.fractions.get(term_id)
+    }
+
+    /// Get an iterator over the features and their fractions in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&TermId, &Fraction)> {
+        self.fractions.iter()
+    }
+
+    /// Get the number of the features assessed in at least one item.
+    pub fn len(&self) -> usize {
+        self.fractions.len()
+    }
+
+    /// Test if no features were assessed.
+    pub fn is_empty(&self) -> bool {
+        self.fractions.is_empty()
+    }
+
+    /// Get the features with their fractions, sorted by the term ID.
+    ///
+    /// ```
+    /// use ontolius::TermId;
+    /// use phenotypes::Fraction;
+    /// use phenotypes::cohort::FeatureFrequencies;
+    /// use phenotypes::simple::SimplePhenotypicFeature;
+    ///
+    /// let polydactyly: TermId = "HP:0010442".parse().unwrap();
+    /// let subject = vec![
+    ///     SimplePhenotypicFeature::new(polydactyly.clone(), Fraction::try_from((1, 1)).unwrap()),
+    /// ];
+    ///
+    /// let frequencies = FeatureFrequencies::from_items([&subject, &subject]);
+    /// let features = frequencies.to_features();
+    ///
+    /// assert_eq!(
+    ///     features,
+    ///     vec![SimplePhenotypicFeature::new(polydactyly, Fraction::try_from((2, 2)).unwrap())],
+    /// );
+    /// ```
+    pub fn to_features(&self) -> Vec<SimplePhenotypicFeature> {
+        let mut features: Vec<_> = self
+            .fractions
+            .iter()
+            .map(|(term_id, fraction)| {
+                SimplePhenotypicFeature::new(term_id.clone(), fraction.clone())
+            })
+            .collect();
+        features.sort_by(|a, b| a.identifier().cmp(b.identifier()));
+        features
+    }
+}
+
+impl From<FeatureFrequencies> for HashMap<TermId, Fraction> {
+    fn from(value: FeatureFrequencies) -> Self {
+        value.fractions
+    }
+}
+
+/// A [`Fraction`] with the references that contributed to its numerator and denominator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourcedFraction {
+    fraction: Fraction,
+    numerator_references: BTreeSet<String>,
+    denominator_references: BTreeSet<String>,
+}
+
+impl SourcedFraction {
+    fn empty() -> Self {
+        Self {
+            fraction: Fraction::try_from((0, 0)).expect("0/0 should be a valid fraction"),
+            numerator_references: BTreeSet::new(),
+            denominator_references: BTreeSet::new(),
+        }
+    }
+
+    fn add(&mut self, fraction: &Fraction, references: &[String]) -> Result<(), PhenotypesError> {
+        self.fraction = self.fraction.checked_add(fraction).ok_or_else(|| {
+            PhenotypesError::CountOutOfRange {
+                value: (u64::from(self.fraction.m()) + u64::from(fraction.m())).to_string(),
+            }
+        })?;
+        if fraction.n() > 0 {
+            self.numerator_references.extend(references.iter().cloned());
+        }
+        if fraction.m() > 0 {
+            self.denominator_references
+                .extend(references.iter().cloned());
+        }
+        Ok(())
+    }
+
+    /// Get the fraction.
+    pub fn fraction(&self) -> &Fraction {
+        &self.fraction
+    }
+
+    /// Get the sorted references of the observations with the feature present.
+    pub fn numerator_references(&self) -> impl Iterator<Item = &str> {
+        self.numerator_references.iter().map(String::as_str)
+    }
+
+    /// Get the sorted references of the observations with the feature present or excluded.
+    pub fn denominator_references(&self) -> impl Iterator<Item = &str> {
+        self.denominator_references.iter().map(String::as_str)
+    }
+}
+
+/// Per-feature [`SourcedFraction`]s of a cohort of items
+/// whose features carry a [`Provenance`].
+///
+/// The items are counted as in [`FeatureFrequencies`].
+/// Alternatively, the fractions reported by the publications can be added directly.
+///
+/// ## Examples
+///
+/// Aggregate the subjects whose features come from different publications:
+///
+/// ```
+/// use ontolius::TermId;
+/// use phenotypes::Fraction;
+/// use phenotypes::cohort::SourcedFeatureFrequencies;
+/// use phenotypes::provenance::{Provenance, WithProvenance};
+/// use phenotypes::simple::SimplePhenotypicFeature;
+///
+/// let seizure: TermId = "HP:0001250".parse().unwrap();
+/// let subject = |n: u32, pmid: &str| vec![WithProvenance::new(
+///     SimplePhenotypicFeature::new(seizure.clone(), Fraction::try_from((n, 1)).unwrap()),
+///     Provenance::default().with_reference(pmid),
+/// )];
+///
+/// let cohort = [
+///     subject(1, "PMID:1"),
+///     subject(0, "PMID:2"),
+///     subject(1, "PMID:3"),
+/// ];
+/// let frequencies = SourcedFeatureFrequencies::from_items(&cohort);
+///
+/// let sourced = frequencies.get(&seizure).unwrap();
+/// assert_eq!(sourced.fraction(), &Fraction::try_from((2, 3)).unwrap());
+/// assert!(sourced.numerator_references().eq(["PMID:1", "PMID:3"]));
+/// assert!(sourced.denominator_references().eq(["PMID:1", "PMID:2", "PMID:3"]));
+/// ```
+///
+/// or add the fractions of the HPO annotations:
+///
+/// ```
+/// use phenotypes::{Fraction, Frequency};
+/// use phenotypes::cohort::SourcedFeatureFrequencies;
+/// use phenotypes::hpoa::HpoaReader;
+/// use phenotypes::provenance::Provenance;
+///
+/// let hpoa = "\
+/// OMIM:619340\tDEE 96\t\tHP:0011097\tPMID:31675180\tPCS\t\t1/2\t\t\tP\tHPO:probinson[2021-06-21]
+/// OMIM:619340\tDEE 96\t\tHP:0011097\tPMID:33098801\tPCS\t\t0/3\t\t\tP\tHPO:probinson[2021-06-21]
+/// ";
+///
+/// let mut frequencies = SourcedFeatureFrequencies::default();
+/// for record in HpoaReader::new(hpoa.as_bytes()) {
+///     let record = record.unwrap();
+///     if let Some(Frequency::Fraction(fraction)) = record.frequency() {
+///         frequencies.add_fraction(record.hpo_id(), fraction, &Provenance::from(&record)).unwrap();
+///     }
+/// }
+///
+/// let sourced = frequencies.get(&"HP:0011097".parse().unwrap()).unwrap();
+/// assert_eq!(sourced.fraction(), &Fraction::try_from((1, 5)).unwrap());
+/// assert!(sourced.numerator_references().eq(["PMID:31675180"]));
+/// assert!(sourced.denominator_references().eq(["PMID:31675180", "PMID:33098801"]));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourcedFeatureFrequencies {
+    fractions: HashMap<TermId, SourcedFraction>,
+}
+
+impl SourcedFeatureFrequencies {
+    /// Aggregate the features of the items.
+    pub fn from_items<'a, I, S>(items: I) -> Self
+    where
+        I: IntoIterator<Item = &'a S>,
+        S: ObservableFeatures + 'a,
+        S::Feature: Identified + ProvenanceAware,
+    {
+        let mut frequencies = SourcedFeatureFrequencies::default();
+        for item in items {
+            frequencies.add_item(item);
+        }
+        frequencies
+    }
+
+    /// Add the features of an item to the aggregate.
+    ///
+    /// The references of all features of the item with the same term ID are pooled.
+    pub fn add_item<S>(&mut self, item: &S)
+    where
+        S: ObservableFeatures,
+        S::Feature: Identified + ProvenanceAware,
+    {
+        let mut present: HashMap<&TermId, Vec<String>> = HashMap::new();
+        for feature in item.present_features() {
+            present
+                .entry(feature.identifier())
+                .or_default()
+                .extend(feature.provenance().references().iter().cloned());
+        }
+        let mut excluded: HashMap<&TermId, Vec<String>> = HashMap::new();
+        for feature in item.excluded_features() {
+            if !present.contains_key(feature.identifier()) {
+                excluded
+                    .entry(feature.identifier())
+                    .or_default()
+                    .extend(feature.provenance().references().iter().cloned());
+            }
+        }
+
+        let one = Fraction::try_from((1, 1)).expect("1/1 should be a valid fraction");
+        let zero = Fraction::try_from((0, 1)).expect("0/1 should be a valid fraction");
+        for (term_id, references) in present {
+            self.entry(term_id)
+                .add(&one, &references)
+                .expect("The count of items should fit in u32");
+        }
+        for (term_id, references) in excluded {
+            self.entry(term_id)
+                .add(&zero, &references)
+                .expect("The count of items should fit in u32");
+        }
+    }
+
+    /// Add the `fraction` of the feature reported by the source with the `provenance`.
+    ///
+    /// Fails with [`PhenotypesError::CountOutOfRange`] if the summed up counts overflow,
+    /// leaving the fraction of the feature unchanged.
+    ///
+    /// ```
+    /// use phenotypes::{Fraction, PhenotypesError};
+    /// use phenotypes::cohort::SourcedFeatureFrequencies;
+    /// use phenotypes::provenance::Provenance;
+    ///
+    /// let seizure = "HP:0001250".parse().unwrap();
+    /// let fraction = Fraction::try_from((1, u32::MAX)).unwrap();
+    /// let provenance = Provenance::default().with_reference("PMID:1");
+    ///
+    /// let mut frequencies = SourcedFeatureFrequencies::default();
+    /// assert!(frequencies.add_fraction(&seizure, &fraction, &provenance).is_ok());
+    /// assert_eq!(
+    ///     frequencies.add_fraction(&seizure, &fraction, &provenance),
+    ///     Err(PhenotypesError::CountOutOfRange { value: "8589934590".to_string() }),
+    /// );
+    /// assert_eq!(frequencies.get(&seizure).unwrap().fraction(), &fraction);
+    /// ```
+    pub fn add_fraction(
+        &mut self,
+        term_id: &TermId,
+        fraction: &Fraction,
+        provenance: &Provenance,
+    ) -> Result<(), PhenotypesError> {
+        self.entry(term_id).add(fraction, provenance.references())
+    }
+
+    fn entry(&mut self, term_id: &TermId) -> &mut SourcedFraction {
+        self.fractions
+            .entry(term_id.clone())
+            .or_insert_with(SourcedFraction::empty)
+    }
+
+    /// Get the sourced fraction of the feature or `None` if the feature was not assessed.
+    pub fn get(&self, term_id: &TermId) -> Option<&SourcedFraction> {
+        self.fractions.get(term_id)
+    }
+
+    /// Get an iterator over the features and their sourced fractions in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&TermId, &SourcedFraction)> {
+        self.fractions.iter()
+    }
+
+    /// Get the number of the assessed features.
+    pub fn len(&self) -> usize {
+        self.fractions.len()
+    }
+
+    /// Test if no features were assessed.
+    pub fn is_empty(&self) -> bool {
+        self.fractions.is_empty()
+    }
+
+    /// Drop the references and keep the fractions.
+    pub fn to_frequencies(&self) -> FeatureFrequencies {
+        FeatureFrequencies {
+            fractions: self
+                .fractions
+                .iter()
+                .map(|(term_id, sourced)| (term_id.clone(), sourced.fraction.clone()))
+                .collect(),
+        }
+    }
+}
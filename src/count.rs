@@ -0,0 +1,134 @@
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Div, Rem, Sub};
+
+/// A `Count` is an unsigned integer that represents the number of annotated items,
+/// such as the numerator and the denominator of a [`crate::Fraction`].
+///
+/// `Count` is implemented for `u8`, `u16`, `u32`, `u64`, `u128`, and `usize`,
+/// and for `num_bigint::BigUint` with the `bigint` feature.
+/// The signed integers and the floats are not counts, hence a `Fraction<i32>`
+/// with negative counts or a `Fraction<f64>` with `NaN` counts cannot be constructed.
+///
+/// ## Examples
+///
+/// ```
+/// use phenotypes::Count;
+///
+/// assert_eq!(u8::zero(), 0);
+/// assert_eq!(Count::checked_add(&250u8, &10), None);
+/// assert_eq!(Count::saturating_add(&250u8, &10), 255);
+/// assert_eq!(Count::to_f64(&7u64), 7.);
+/// ```
+pub trait Count:
+    Clone
+    + Ord
+    + Hash
+    + Debug
+    + Display
+    + Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    /// Get the zero count.
+    fn zero() -> Self;
+
+    /// Convert the count into a float, possibly losing precision for large counts.
+    fn to_f64(&self) -> f64;
+
+    /// Add the counts, returning `None` on overflow.
+    fn checked_add(&self, rhs: &Self) -> Option<Self>;
+
+    /// Add the counts, saturating at the maximum count.
+    fn saturating_add(&self, rhs: &Self) -> Self;
+}
+
+macro_rules! impl_count {
+    ($($t:ty),*) => {
+        $(
+            impl Count for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn to_f64(&self) -> f64 {
+                    *self as f64
+                }
+
+                fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *rhs)
+                }
+
+                fn saturating_add(&self, rhs: &Self) -> Self {
+                    <$t>::saturating_add(*self, *rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_count!(u8, u16, u32, u64, u128, usize);
+
+/// The arbitrary precision count never overflows.
+///
+/// ```
+/// use num_bigint::BigUint;
+/// use phenotypes::Fraction;
+///
+/// let big = BigUint::from(u128::MAX);
+/// let f = Fraction::try_from((big.clone(), big.clone())).unwrap();
+///
+/// let sum = f.checked_add(&f).unwrap();
+/// assert_eq!(sum.m(), big * 2u8);
+/// ```
+#[cfg(feature = "bigint")]
+impl Count for num_bigint::BigUint {
+    fn zero() -> Self {
+        num_bigint::BigUint::ZERO
+    }
+
+    fn to_f64(&self) -> f64 {
+        num_traits::ToPrimitive::to_f64(self).unwrap_or(f64::INFINITY)
+    }
+
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(self + rhs)
+    }
+
+    fn saturating_add(&self, rhs: &Self) -> Self {
+        self + rhs
+    }
+}
+
+/// A float type that the ratios of [`Count`]s, such as [`crate::Fraction::ratio`],
+/// can be computed in.
+///
+/// `RatioFloat` is implemented for `f32` and `f64`.
+/// The ratios are computed in `f64` and then converted into the float type.
+///
+/// ## Examples
+///
+/// ```
+/// use phenotypes::RatioFloat;
+///
+/// assert_eq!(f32::from_f64(0.125), 0.125f32);
+/// assert_eq!(f64::from_f64(0.125), 0.125);
+/// ```
+pub trait RatioFloat: Copy {
+    /// Convert the `f64` value into the float type, possibly losing precision.
+    fn from_f64(value: f64) -> Self;
+}
+
+impl RatioFloat for f32 {
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl RatioFloat for f64 {
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
@@ -0,0 +1,372 @@
+//! A module for phenotype-driven differential diagnosis based on likelihood ratios.
+//!
+//! The approach follows [LIRICAL](https://doi.org/10.1016/j.ajhg.2020.06.021).
+//! For each disease, we compute the likelihood ratio (LR) of each present and excluded
+//! feature of the patient, given the disease and the background frequency of the feature
+//! in all diseases. The product of the feature LRs, the composite LR, updates
+//! the pretest probability of the disease into the post-test probability,
+//! which is used to rank the diseases.
+//!
+//! ## Likelihood ratios
+//!
+//! The LR of a feature `q` is the probability of `q` given the disease
+//! divided by the background probability `bg(q)` of `q` across all diseases.
+//! The disease features are matched to `q` along the ontology hierarchy:
+//!
+//! * [`MatchType::Exact`] or [`MatchType::Implied`] - the disease is annotated with `q`
+//!   or with its descendant `t`, and `q` is implied by the true path rule.
+//!   The LR of a present `q` is `f(t) / bg(q)`, where `f(t)` is the frequency of `t`
+//!   in the disease, and the LR of an excluded `q` is `(1 - f(t)) / (1 - bg(q))`.
+//! * [`MatchType::Ancestor`] - the disease is annotated with an ancestor `t` of `q`.
+//!   The probability of `q` given the disease is apportioned from `f(t)`
+//!   by the background frequencies, and the LR of a present `q` is `f(t) / bg(t)`.
+//!   The excluded features are not affected by the ancestor annotations.
+//! * [`MatchType::NoMatch`] - the LR of a present `q` is the `noise`,
+//!   the probability of a false positive feature, and the LR of an excluded `q`
+//!   is `1 / (1 - bg(q))`.
+//!
+//! The frequency of a disease feature is given by [`FrequencyAware`].
+//! A present feature with unknown frequency is assumed to be obligate,
+//! and an excluded feature is assumed to have zero frequency.
+//! The best match is used if several disease features match `q`,
+//! and the LRs as well as the background frequencies are bounded by the `noise`
+//! to prevent a single feature from vetoing a disease.
+//!
+//! ## Examples
+//!
+//! ```
+//! # let hpo = include!("../tests/common/toy_hpo.rs");
+//! use ontolius::{Identified, TermId};
+//! use phenotypes::{Fraction, ObservableFeatures, ObservationState, Observable};
+//! use phenotypes::diagnosis::{DifferentialDiagnosis, MatchType};
+//! use phenotypes::simple::SimplePhenotypicFeature;
+//!
+//! struct Disease(TermId, Vec<SimplePhenotypicFeature>);
+//!
+//! impl Identified for Disease {
+//!     fn identifier(&self) -> &TermId {
+//!         &self.0
+//!     }
+//! }
+//!
+//! impl ObservableFeatures for Disease {
+//!     type Feature = SimplePhenotypicFeature;
+//!
+//!     fn present_features(&self) -> impl Iterator<Item = &Self::Feature> {
+//!         self.1.present_features()
+//!     }
+//!
+//!     fn excluded_features(&self) -> impl Iterator<Item = &Self::Feature> {
+//!         self.1.excluded_features()
+//!     }
+//! }
+//!
+//! let feature = |curie: &str, n, m| SimplePhenotypicFeature::new(
+//!     curie.parse().unwrap(),
+//!     Fraction::try_from((n, m)).unwrap(),
+//! );
+//! let diseases = [
+//!     Disease("OMIM:100000".parse().unwrap(), vec![feature("HP:0100259", 9, 10)]),
+//!     Disease("OMIM:200000".parse().unwrap(), vec![feature("HP:0001250", 8, 10)]),
+//!     Disease(
+//!         "OMIM:300000".parse().unwrap(),
+//!         vec![feature("HP:0010442", 5, 10), feature("HP:0001250", 5, 10)],
+//!     ),
+//! ];
+//!
+//! struct Observation(TermId, ObservationState);
+//!
+//! impl Identified for Observation {
+//!     fn identifier(&self) -> &TermId {
+//!         &self.0
+//!     }
+//! }
+//!
+//! impl Observable for Observation {
+//!     fn observation_state(&self) -> ObservationState {
+//!         self.1
+//!     }
+//! }
+//!
+//! let patient = vec![
+//!     Observation("HP:0100259".parse().unwrap(), ObservationState::Present),
+//!     Observation("HP:0001250".parse().unwrap(), ObservationState::Excluded),
+//! ];
+//!
+//! // `hpo` is a small hand-built ontology with the HPO terms used in the example.
+//! let diagnosis = DifferentialDiagnosis::new(&hpo, &diseases);
+//! let results = diagnosis.rank(&patient);
+//!
+//! let ranking: Vec<_> = results.iter().map(|r| r.disease_id().to_string()).collect();
+//! assert_eq!(ranking, ["OMIM:100000", "OMIM:300000", "OMIM:200000"]);
+//!
+//! let best = &results[0];
+//! assert_eq!(best.feature_ratios()[0].match_type(), MatchType::Exact);
+//! // Postaxial polydactyly: `0.9 / bg` where `bg = 0.9 / 3` ...
+//! // ... and excluded seizure: `1 / (1 - bg)` where `bg = (0.8 + 0.5) / 3`.
+//! assert!((best.composite_ratio() - 3. * 3. / 1.7).abs() < 1e-9);
+//! assert!(best.post_test_probability() > 0.7);
+//! ```
+use std::collections::HashMap;
+
+use ontolius::ontology::{HierarchyQueries, HierarchyWalks};
+use ontolius::{Identified, TermId};
+
+use crate::{FrequencyAware, ObservableFeatures, ObservationState};
+
+/// The way a patient feature was matched to the disease features.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MatchType {
+    /// The disease is annotated with the patient feature.
+    Exact,
+    /// The disease is annotated with a descendant of the patient feature.
+    Implied,
+    /// The disease is annotated with an ancestor of the patient feature.
+    Ancestor,
+    /// The disease has no related annotation.
+    NoMatch,
+}
+
+/// The likelihood ratio of a patient feature given a disease.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeatureLikelihoodRatio {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
+    term_id: TermId,
+    state: ObservationState,
+    match_type: MatchType,
+    ratio: f64,
+}
+
+impl FeatureLikelihoodRatio {
+    /// Get the ID of the patient feature.
+    pub fn term_id(&self) -> &TermId {
+        &self.term_id
+    }
+
+    /// Get the state of the feature in the patient.
+    pub fn state(&self) -> ObservationState {
+        self.state
+    }
+
+    /// Get the way the feature was matched to the disease features.
+    pub fn match_type(&self) -> MatchType {
+        self.match_type
+    }
+
+    /// Get the likelihood ratio.
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+}
+
+/// The result of testing a disease as the diagnosis of the patient.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiagnosisResult {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
+    disease_id: TermId,
+    feature_ratios: Vec<FeatureLikelihoodRatio>,
+    composite_ratio: f64,
+    post_test_probability: f64,
+}
+
+impl DiagnosisResult {
+    /// Get the ID of the disease.
+    pub fn disease_id(&self) -> &TermId {
+        &self.disease_id
+    }
+
+    /// Get the likelihood ratios of the present and excluded patient features.
+    pub fn feature_ratios(&self) -> &[FeatureLikelihoodRatio] {
+        &self.feature_ratios
+    }
+
+    /// Get the product of the feature likelihood ratios.
+    pub fn composite_ratio(&self) -> f64 {
+        self.composite_ratio
+    }
+
+    /// Get the probability of the disease after observing the patient features.
+    pub fn post_test_probability(&self) -> f64 {
+        self.post_test_probability
+    }
+}
+
+/// The default probability of a false positive feature.
+const DEFAULT_NOISE: f64 = 0.01;
+
+/// Differential diagnosis of a patient against a set of diseases.
+///
+/// The background frequencies of the features are computed from the diseases
+/// upon creation, and the pretest probability is uniform across the diseases.
+pub struct DifferentialDiagnosis<'a, O, D> {
+    hierarchy: &'a O,
+    diseases: &'a [D],
+    background: HashMap<TermId, f64>,
+    noise: f64,
+}
+
+impl<'a, O, D> DifferentialDiagnosis<'a, O, D>
+where
+    O: HierarchyWalks + HierarchyQueries,
+    D: Identified + ObservableFeatures,
+    D::Feature: Identified + FrequencyAware,
+{
+    pub fn new(hierarchy: &'a O, diseases: &'a [D]) -> Self {
+        // `bg(t)` is the mean probability of observing `t` or its descendant in a disease.
+        let mut background: HashMap<TermId, f64> = HashMap::new();
+        for disease in diseases {
+            let mut probabilities: HashMap<&TermId, f64> = HashMap::new();
+            for (feature, f) in disease_frequencies(disease) {
+                for term_id in hierarchy.iter_term_and_ancestor_ids(feature) {
+                    let p = probabilities.entry(term_id).or_default();
+                    *p = p.max(f);
+                }
+            }
+            for (term_id, p) in probabilities {
+                *background.entry(term_id.clone()).or_default() += p;
+            }
+        }
+        let n_diseases = diseases.len().max(1) as f64;
+        background.values_mut().for_each(|p| *p /= n_diseases);
+
+        Self {
+            hierarchy,
+            diseases,
+            background,
+            noise: DEFAULT_NOISE,
+        }
+    }
+
+    /// Set the probability of a false positive feature, `0.01` by default.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the `noise` is not in the open interval `(0, 1)`.
+    #[must_use]
+    pub fn with_noise(mut self, noise: f64) -> Self {
+        assert!(
+            noise > 0. && noise < 1.,
+            "Noise must be in (0, 1) but was {noise}"
+        );
+        self.noise = noise;
+        self
+    }
+
+    /// Get the background frequency of the feature across the diseases.
+    pub fn background_frequency(&self, term_id: &TermId) -> f64 {
+        self.background
+            .get(term_id)
+            .copied()
+            .unwrap_or_default()
+            .clamp(self.noise, 1. - self.noise)
+    }
+
+    /// Rank the diseases by the post-test probability given the `patient` features,
+    /// starting from the most probable diagnosis.
+    pub fn rank<P>(&self, patient: &P) -> Vec<DiagnosisResult>
+    where
+        P: ObservableFeatures,
+        P::Feature: Identified,
+    {
+        let pretest_odds = if self.diseases.len() > 1 {
+            1. / (self.diseases.len() - 1) as f64
+        } else {
+            f64::INFINITY
+        };
+
+        let mut results: Vec<_> = self
+            .diseases
+            .iter()
+            .map(|disease| {
+                let frequencies: Vec<_> = disease_frequencies(disease).collect();
+                let feature_ratios: Vec<_> = patient
+                    .present_features()
+                    .map(|q| (q, ObservationState::Present))
+                    .chain(
+                        patient
+                            .excluded_features()
+                            .map(|q| (q, ObservationState::Excluded)),
+                    )
+                    .map(|(q, state)| self.likelihood_ratio(q.identifier(), state, &frequencies))
+                    .collect();
+                let ln_ratio: f64 = feature_ratios.iter().map(|lr| lr.ratio.ln()).sum();
+                let ln_odds = pretest_odds.ln() + ln_ratio;
+
+                DiagnosisResult {
+                    disease_id: disease.identifier().clone(),
+                    feature_ratios,
+                    composite_ratio: ln_ratio.exp(),
+                    post_test_probability: 1. / (1. + (-ln_odds).exp()),
+                }
+            })
+            .collect();
+
+        results.sort_by(|a, b| {
+            b.post_test_probability
+                .total_cmp(&a.post_test_probability)
+                .then_with(|| b.composite_ratio.total_cmp(&a.composite_ratio))
+                .then_with(|| a.disease_id.cmp(&b.disease_id))
+        });
+        results
+    }
+
+    fn likelihood_ratio(
+        &self,
+        q: &TermId,
+        state: ObservationState,
+        frequencies: &[(&TermId, f64)],
+    ) -> FeatureLikelihoodRatio {
+        // The best exact or implied match.
+        let mut best: Option<(MatchType, f64)> = None;
+        for &(t, f) in frequencies {
+            let match_type = if t == q {
+                MatchType::Exact
+            } else if self.hierarchy.is_descendant_of(t, q) {
+                MatchType::Implied
+            } else {
+                continue;
+            };
+            if best.is_none_or(|(_, g)| f > g) {
+                best = Some((match_type, f));
+            }
+        }
+
+        let bg = self.background_frequency(q);
+        let (match_type, ratio) = match (state, best) {
+            (ObservationState::Excluded, Some((match_type, f))) => {
+                (match_type, (1. - f) / (1. - bg))
+            }
+            (ObservationState::Excluded, None) => (MatchType::NoMatch, 1. / (1. - bg)),
+            (_, Some((match_type, f))) => (match_type, f / bg),
+            (_, None) => frequencies
+                .iter()
+                .filter(|(t, _)| self.hierarchy.is_ancestor_of(*t, q))
+                .map(|&(t, f)| f / self.background_frequency(t))
+                .max_by(f64::total_cmp)
+                .map(|ratio| (MatchType::Ancestor, ratio))
+                .unwrap_or((MatchType::NoMatch, self.noise)),
+        };
+
+        FeatureLikelihoodRatio {
+            term_id: q.clone(),
+            state,
+            match_type,
+            ratio: ratio.max(self.noise),
+        }
+    }
+}
+
+/// Get the disease features along with their frequencies.
+fn disease_frequencies<D>(disease: &D) -> impl Iterator<Item = (&TermId, f64)>
+where
+    D: ObservableFeatures,
+    D::Feature: Identified + FrequencyAware,
+{
+    disease
+        .present_features()
+        .map(|f| (f.identifier(), f.probability().unwrap_or(1.)))
+        .chain(disease.excluded_features().map(|f| (f.identifier(), 0.)))
+}
@@ -0,0 +1,182 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+use ontolius::TermId;
+
+use crate::FractionPart;
+
+/// The error type of the crate.
+///
+/// ## Examples
+///
+/// The variants carry the offending values and can be matched on:
+///
+/// ```
+/// use phenotypes::{Fraction, PhenotypesError};
+///
+/// let err = Fraction::try_from((5u32, 3)).unwrap_err();
+///
+/// match &err {
+///     PhenotypesError::NumeratorExceedsDenominator { n, m } => {
+///         assert_eq!(n, "5");
+///         assert_eq!(m, "3");
+///     }
+///     _ => unreachable!(),
+/// }
+/// assert_eq!(err.to_string(), "numerator 5 must be less than or equal to denominator 3");
+/// ```
+///
+/// The I/O errors keep the underlying [`std::io::Error`] as their source:
+///
+/// ```
+/// use std::error::Error;
+/// use std::io::ErrorKind;
+/// use phenotypes::PhenotypesError;
+/// use phenotypes::hpoa::HpoaReader;
+///
+/// let invalid_utf8: &[u8] = b"OMIM:619340\tDEE \xff\n";
+/// let err = HpoaReader::new(invalid_utf8).next().unwrap().unwrap_err();
+///
+/// assert!(matches!(err, PhenotypesError::Io { line: 1, .. }));
+/// let source = err.source().unwrap().downcast_ref::<std::io::Error>().unwrap();
+/// assert_eq!(source.kind(), ErrorKind::InvalidData);
+/// ```
+///
+/// The I/O errors are equal if they occurred at the same line and have the same
+/// [`std::io::ErrorKind`], since [`std::io::Error`] cannot be compared.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum PhenotypesError {
+    /// The numerator *n* is greater than the denominator *m*.
+    NumeratorExceedsDenominator { n: String, m: String },
+    /// The count is negative.
+    NegativeCount { value: String },
+    /// The count cannot be represented by the count type.
+    CountOutOfRange { value: String },
+    /// The denominator is zero where a non-zero denominator is required,
+    /// e.g. to compute a ratio.
+    ZeroDenominator,
+    /// The `input` cannot be parsed into a [`crate::Fraction`]
+    /// due to an invalid `part`.
+    ParseFraction { input: String, part: FractionPart },
+    /// The term ID is not one of the expected ontology terms.
+    UnexpectedTerm { term_id: TermId, expected: String },
+    /// The JSON input does not match the expected data model.
+    InvalidJson { reason: String },
+    /// The `value` is not one of the `expected` values.
+    InvalidValue { value: String, expected: String },
+    /// The record at the 1-based `line` of the input is malformed.
+    MalformedRecord { line: usize, reason: String },
+    /// Reading the 1-based `line` of the input failed.
+    Io {
+        line: usize,
+        source: Arc<std::io::Error>,
+    },
+}
+
+impl Display for PhenotypesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PhenotypesError::NumeratorExceedsDenominator { n, m } => {
+                write!(
+                    f,
+                    "numerator {n} must be less than or equal to denominator {m}"
+                )
+            }
+            PhenotypesError::NegativeCount { value } => {
+                write!(f, "count {value} must not be negative")
+            }
+            PhenotypesError::CountOutOfRange { value } => {
+                write!(f, "count {value} is out of range of the count type")
+            }
+            PhenotypesError::ZeroDenominator => f.write_str("denominator must not be zero"),
+            PhenotypesError::ParseFraction { input, part } => {
+                let reason = match part {
+                    FractionPart::Format => "expected `n/m` or a percentage",
+                    FractionPart::Numerator => "invalid numerator",
+                    FractionPart::Denominator => "invalid denominator",
+                    FractionPart::Percentage => "invalid percentage",
+                };
+                write!(f, "cannot parse fraction from {input:?}: {reason}")
+            }
+            PhenotypesError::UnexpectedTerm { term_id, expected } => {
+                write!(f, "{term_id} is not {expected}")
+            }
+            PhenotypesError::InvalidJson { reason } => write!(f, "invalid JSON: {reason}"),
+            PhenotypesError::InvalidValue { value, expected } => {
+                write!(f, "invalid value {value:?}, expected {expected}")
+            }
+            PhenotypesError::MalformedRecord { line, reason } => {
+                write!(f, "malformed record at line {line}: {reason}")
+            }
+            PhenotypesError::Io { line, source } => {
+                write!(f, "I/O error at line {line}: {source}")
+            }
+        }
+    }
+}
+
+impl Error for PhenotypesError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PhenotypesError::Io { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for PhenotypesError {
+    fn eq(&self, other: &Self) -> bool {
+        use PhenotypesError::*;
+
+        match (self, other) {
+            (
+                NumeratorExceedsDenominator { n, m },
+                NumeratorExceedsDenominator { n: o_n, m: o_m },
+            ) => n == o_n && m == o_m,
+            (NegativeCount { value }, NegativeCount { value: o_value })
+            | (CountOutOfRange { value }, CountOutOfRange { value: o_value }) => value == o_value,
+            (ZeroDenominator, ZeroDenominator) => true,
+            (
+                ParseFraction { input, part },
+                ParseFraction {
+                    input: o_input,
+                    part: o_part,
+                },
+            ) => input == o_input && part == o_part,
+            (
+                UnexpectedTerm { term_id, expected },
+                UnexpectedTerm {
+                    term_id: o_term_id,
+                    expected: o_expected,
+                },
+            ) => term_id == o_term_id && expected == o_expected,
+            (InvalidJson { reason }, InvalidJson { reason: o_reason }) => reason == o_reason,
+            (
+                InvalidValue { value, expected },
+                InvalidValue {
+                    value: o_value,
+                    expected: o_expected,
+                },
+            ) => value == o_value && expected == o_expected,
+            (
+                MalformedRecord { line, reason },
+                MalformedRecord {
+                    line: o_line,
+                    reason: o_reason,
+                },
+            ) => line == o_line && reason == o_reason,
+            (
+                Io { line, source },
+                Io {
+                    line: o_line,
+                    source: o_source,
+                },
+            ) => line == o_line && source.kind() == o_source.kind(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for PhenotypesError {}
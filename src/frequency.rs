@@ -0,0 +1,284 @@
+use std::fmt::{Display, Formatter};
+use std::sync::LazyLock;
+
+use ontolius::{Identified, TermId};
+
+use crate::{Count, Fraction, PhenotypesError};
+
+static OBLIGATE: LazyLock<TermId> = LazyLock::new(|| TermId::from(("HP", "0040280")));
+static VERY_FREQUENT: LazyLock<TermId> = LazyLock::new(|| TermId::from(("HP", "0040281")));
+static FREQUENT: LazyLock<TermId> = LazyLock::new(|| TermId::from(("HP", "0040282")));
+static OCCASIONAL: LazyLock<TermId> = LazyLock::new(|| TermId::from(("HP", "0040283")));
+static VERY_RARE: LazyLock<TermId> = LazyLock::new(|| TermId::from(("HP", "0040284")));
+static EXCLUDED: LazyLock<TermId> = LazyLock::new(|| TermId::from(("HP", "0040285")));
+
+/// A `FrequencyCategory` corresponds to one of the terms
+/// of the [Frequency (HP:0040279)](https://hpo.jax.org/browse/term/HP:0040279) HPO sub-module.
+///
+/// HPO annotations express the frequency of a feature either as *n* of *m* [`Fraction`]
+/// or as a frequency term with an official percentage band.
+///
+/// ## Examples
+///
+/// Map a [`Fraction`] to its category:
+///
+/// ```
+/// use phenotypes::{Fraction, FrequencyCategory};
+///
+/// let f = Fraction::try_from((7u32, 10)).unwrap();
+/// let category = FrequencyCategory::try_from(&f).unwrap();
+///
+/// assert_eq!(category, FrequencyCategory::Frequent);
+/// assert_eq!(category.term_id().to_string(), "HP:0040282");
+/// ```
+///
+/// or get the category of an HPO term ID:
+///
+/// ```
+/// use ontolius::TermId;
+/// use phenotypes::FrequencyCategory;
+///
+/// let term_id: TermId = "HP:0040281".parse().unwrap();
+/// let category = FrequencyCategory::try_from(&term_id).unwrap();
+///
+/// assert_eq!(category, FrequencyCategory::VeryFrequent);
+/// assert_eq!(category.probability(), 0.895);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FrequencyCategory {
+    /// [Obligate (HP:0040280)](https://hpo.jax.org/browse/term/HP:0040280),
+    /// the feature is always present (100%).
+    Obligate,
+    /// [Very frequent (HP:0040281)](https://hpo.jax.org/browse/term/HP:0040281),
+    /// the feature is present in 80% to 99% of the cases.
+    VeryFrequent,
+    /// [Frequent (HP:0040282)](https://hpo.jax.org/browse/term/HP:0040282),
+    /// the feature is present in 30% to 79% of the cases.
+    Frequent,
+    /// [Occasional (HP:0040283)](https://hpo.jax.org/browse/term/HP:0040283),
+    /// the feature is present in 5% to 29% of the cases.
+    Occasional,
+    /// [Very rare (HP:0040284)](https://hpo.jax.org/browse/term/HP:0040284),
+    /// the feature is present in 1% to 4% of the cases.
+    VeryRare,
+    /// [Excluded (HP:0040285)](https://hpo.jax.org/browse/term/HP:0040285),
+    /// the feature is never present (0%).
+    Excluded,
+}
+
+impl FrequencyCategory {
+    /// Get the ID of the HPO term that corresponds to the category.
+    pub fn term_id(&self) -> &'static TermId {
+        match self {
+            FrequencyCategory::Obligate => &OBLIGATE,
+            FrequencyCategory::VeryFrequent => &VERY_FREQUENT,
+            FrequencyCategory::Frequent => &FREQUENT,
+            FrequencyCategory::Occasional => &OCCASIONAL,
+            FrequencyCategory::VeryRare => &VERY_RARE,
+            FrequencyCategory::Excluded => &EXCLUDED,
+        }
+    }
+
+    /// Get the representative probability of the category,
+    /// i.e. the midpoint of its percentage band.
+    pub fn probability(&self) -> f64 {
+        match self {
+            FrequencyCategory::Obligate => 1.,
+            FrequencyCategory::VeryFrequent => 0.895,
+            FrequencyCategory::Frequent => 0.545,
+            FrequencyCategory::Occasional => 0.17,
+            FrequencyCategory::VeryRare => 0.025,
+            FrequencyCategory::Excluded => 0.,
+        }
+    }
+
+    /// Get the category that includes the `ratio`.
+    ///
+    /// The ratios between the official percentage bands are assigned
+    /// to the lower band (e.g. `0.795` is [`FrequencyCategory::Frequent`])
+    /// and the non-zero ratios below 1% are [`FrequencyCategory::VeryRare`].
+    ///
+    /// Returns `None` if the `ratio` is not in the `[0, 1]` range.
+    pub fn from_ratio(ratio: f64) -> Option<Self> {
+        if !(0. ..=1.).contains(&ratio) {
+            None
+        } else if ratio == 1. {
+            Some(FrequencyCategory::Obligate)
+        } else if ratio >= 0.8 {
+            Some(FrequencyCategory::VeryFrequent)
+        } else if ratio >= 0.3 {
+            Some(FrequencyCategory::Frequent)
+        } else if ratio >= 0.05 {
+            Some(FrequencyCategory::Occasional)
+        } else if ratio > 0. {
+            Some(FrequencyCategory::VeryRare)
+        } else {
+            Some(FrequencyCategory::Excluded)
+        }
+    }
+}
+
+impl Identified for FrequencyCategory {
+    fn identifier(&self) -> &TermId {
+        self.term_id()
+    }
+}
+
+impl Display for FrequencyCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            FrequencyCategory::Obligate => "Obligate",
+            FrequencyCategory::VeryFrequent => "Very frequent",
+            FrequencyCategory::Frequent => "Frequent",
+            FrequencyCategory::Occasional => "Occasional",
+            FrequencyCategory::VeryRare => "Very rare",
+            FrequencyCategory::Excluded => "Excluded",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Get the category of an HPO frequency term ID.
+///
+/// Fails if the term ID is not one of the frequency terms.
+impl TryFrom<&TermId> for FrequencyCategory {
+    type Error = PhenotypesError;
+
+    fn try_from(value: &TermId) -> Result<Self, Self::Error> {
+        [
+            FrequencyCategory::Obligate,
+            FrequencyCategory::VeryFrequent,
+            FrequencyCategory::Frequent,
+            FrequencyCategory::Occasional,
+            FrequencyCategory::VeryRare,
+            FrequencyCategory::Excluded,
+        ]
+        .into_iter()
+        .find(|category| category.term_id() == value)
+        .ok_or_else(|| PhenotypesError::UnexpectedTerm {
+            term_id: value.clone(),
+            expected: "an HPO frequency term".to_string(),
+        })
+    }
+}
+
+/// Get the category that includes the ratio of the `Fraction`.
+///
+/// Fails for the `0/0` fraction, since its ratio is undefined.
+///
+/// ```
+/// use phenotypes::{Fraction, FrequencyCategory, PhenotypesError};
+///
+/// let all = Fraction::try_from((10u32, 10)).unwrap();
+/// assert_eq!(FrequencyCategory::try_from(&all), Ok(FrequencyCategory::Obligate));
+///
+/// let none = Fraction::try_from((0u32, 10)).unwrap();
+/// assert_eq!(FrequencyCategory::try_from(&none), Ok(FrequencyCategory::Excluded));
+///
+/// let empty = Fraction::try_from((0u32, 0)).unwrap();
+/// assert_eq!(FrequencyCategory::try_from(&empty), Err(PhenotypesError::ZeroDenominator));
+/// ```
+impl<T> TryFrom<&Fraction<T>> for FrequencyCategory
+where
+    T: Count,
+{
+    type Error = PhenotypesError;
+
+    fn try_from(value: &Fraction<T>) -> Result<Self, Self::Error> {
+        let m: f64 = value.m().to_f64();
+        if m == 0. {
+            Err(PhenotypesError::ZeroDenominator)
+        } else {
+            let n: f64 = value.n().to_f64();
+            // `Fraction` guarantees `n <= m`, hence the ratio is always in `[0, 1]`.
+            Ok(FrequencyCategory::from_ratio(n / m).expect("Ratio should be in [0, 1]"))
+        }
+    }
+}
+
+/// An entity, such as a disease feature, with a known frequency in the annotated items.
+pub trait FrequencyAware {
+    /// Get the probability of observing the entity in an annotated item
+    /// or `None` if the frequency is not known.
+    fn probability(&self) -> Option<f64>;
+}
+
+/// The `Frequency` of a feature expressed in either of the HPO annotation styles.
+///
+/// ```
+/// use phenotypes::{Fraction, Frequency, FrequencyCategory};
+///
+/// let fraction: Frequency = Fraction::try_from((3, 4)).unwrap().into();
+/// assert_eq!(fraction.probability(), Some(0.75));
+/// assert_eq!(fraction.category(), Some(FrequencyCategory::Frequent));
+///
+/// let category: Frequency = FrequencyCategory::Occasional.into();
+/// assert_eq!(category.probability(), Some(0.17));
+///
+/// let percentage = Frequency::Percentage(17.);
+/// assert_eq!(percentage.probability(), Some(0.17));
+/// assert_eq!(percentage.category(), Some(FrequencyCategory::Occasional));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Frequency {
+    /// The frequency is known as *n* of *m* items.
+    Fraction(Fraction),
+    /// The frequency is known as one of the HPO frequency terms.
+    Category(FrequencyCategory),
+    /// The frequency is known as a percentage in the `[0, 100]` range, such as `17%`.
+    ///
+    /// A percentage carries no counts, hence it is not a [`Fraction`].
+    Percentage(f64),
+}
+
+impl Frequency {
+    /// Get the probability of the feature.
+    ///
+    /// The ratio is used for a fraction, the representative probability for a category,
+    /// and the percentage divided by `100` for a percentage.
+    /// Returns `None` for the `0/0` fraction and for a percentage out of the `[0, 100]` range.
+    pub fn probability(&self) -> Option<f64> {
+        match self {
+            Frequency::Fraction(fraction) => {
+                if fraction.m() == 0 {
+                    None
+                } else {
+                    Some(f64::from(fraction.n()) / f64::from(fraction.m()))
+                }
+            }
+            Frequency::Category(category) => Some(category.probability()),
+            Frequency::Percentage(percentage) => {
+                if (0. ..=100.).contains(percentage) {
+                    Some(percentage / 100.)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Get the frequency category.
+    ///
+    /// Returns `None` for the `0/0` fraction and for a percentage out of the `[0, 100]` range.
+    pub fn category(&self) -> Option<FrequencyCategory> {
+        match self {
+            Frequency::Fraction(fraction) => FrequencyCategory::try_from(fraction).ok(),
+            Frequency::Category(category) => Some(*category),
+            Frequency::Percentage(_) => self.probability().and_then(FrequencyCategory::from_ratio),
+        }
+    }
+}
+
+impl From<Fraction> for Frequency {
+    fn from(value: Fraction) -> Self {
+        Frequency::Fraction(value)
+    }
+}
+
+impl From<FrequencyCategory> for Frequency {
+    fn from(value: FrequencyCategory) -> Self {
+        Frequency::Category(value)
+    }
+}
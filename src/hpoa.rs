@@ -0,0 +1,642 @@
+//! A module for reading HPO disease annotations from the `phenotype.hpoa` file.
+//!
+//! The [`HpoaReader`] streams the [`HpoaRecord`]s from the annotation file,
+//! one record per line. The records of a disease are stored in consecutive lines,
+//! and the reader can group them into [`DiseaseAnnotations`].
+//!
+//! See the [HPO documentation](https://obophenotype.github.io/human-phenotype-ontology/annotations/phenotype_hpoa/)
+//! for more info regarding the file format.
+//!
+//! ## Examples
+//!
+//! ```
+//! use phenotypes::{Fraction, Frequency, ObservableFeatures};
+//! use phenotypes::hpoa::HpoaReader;
+//!
+//! let hpoa = "\
+//! #description: \"HPO annotations for rare diseases\"
+//! #version: 2024-04-26
+//! database_id\tdisease_name\tqualifier\thpo_id\treference\tevidence\tonset\tfrequency\tsex\tmodifier\taspect\tbiocuration
+//! OMIM:619340\tDevelopmental and epileptic encephalopathy 96\t\tHP:0011097\tPMID:31675180\tPCS\t\t1/2\t\t\tP\tHPO:probinson[2021-06-21]
+//! OMIM:619340\tDevelopmental and epileptic encephalopathy 96\tNOT\tHP:0001250\tPMID:31675180\tPCS\t\t\t\t\tP\tHPO:probinson[2021-06-21]
+//! OMIM:619340\tDevelopmental and epileptic encephalopathy 96\t\tHP:0000007\tPMID:31675180\tPCS\t\t\t\t\tI\tHPO:probinson[2021-06-21]
+//! ORPHA:1899\tArthrogryposis multiplex congenita\t\tHP:0002804\tORPHA:1899\tTAS\t\tHP:0040280\t\t\tP\tORPHA:orphadata[2024-04-26]
+//! ";
+//!
+//! let diseases: Vec<_> = HpoaReader::new(hpoa.as_bytes())
+//!     .diseases()
+//!     .collect::<Result<_, _>>()
+//!     .expect("The annotations are well formed");
+//!
+//! assert_eq!(diseases.len(), 2);
+//!
+//! let dee96 = &diseases[0];
+//! assert_eq!(dee96.disease_id().to_string(), "OMIM:619340");
+//! assert_eq!(dee96.records().len(), 3);
+//! assert_eq!(dee96.present_feature_count(), 1);
+//! assert_eq!(dee96.excluded_feature_count(), 1);
+//!
+//! let record = dee96.present_features().next().unwrap();
+//! assert_eq!(record.frequency(), Some(&Frequency::Fraction(Fraction::try_from((1, 2)).unwrap())));
+//! ```
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use ontolius::{Identified, TermId};
+
+use crate::{
+    Fraction, Frequency, FrequencyAware, FrequencyCategory, Observable, ObservableFeatures,
+    ObservationState, PercentagePolicy, PhenotypesError, Sex,
+};
+
+/// The number of tab-separated columns of an annotation line.
+const N_COLUMNS: usize = 12;
+
+/// The evidence supporting an annotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EvidenceCode {
+    /// Inferred from electronic annotation (`IEA`).
+    InferredFromElectronicAnnotation,
+    /// Published clinical study (`PCS`).
+    PublishedClinicalStudy,
+    /// Traceable author statement (`TAS`),
+    /// e.g. a review article or a textbook.
+    TraceableAuthorStatement,
+}
+
+impl FromStr for EvidenceCode {
+    type Err = PhenotypesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "IEA" => Ok(EvidenceCode::InferredFromElectronicAnnotation),
+            "PCS" => Ok(EvidenceCode::PublishedClinicalStudy),
+            "TAS" => Ok(EvidenceCode::TraceableAuthorStatement),
+            _ => Err(PhenotypesError::InvalidValue {
+                value: s.to_string(),
+                expected: "one of `IEA`, `PCS`, or `TAS`".to_string(),
+            }),
+        }
+    }
+}
+
+impl Display for EvidenceCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            EvidenceCode::InferredFromElectronicAnnotation => "IEA",
+            EvidenceCode::PublishedClinicalStudy => "PCS",
+            EvidenceCode::TraceableAuthorStatement => "TAS",
+        })
+    }
+}
+
+/// The HPO sub-ontology of the annotated term.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Aspect {
+    /// Phenotypic abnormality (`P`).
+    PhenotypicAbnormality,
+    /// Mode of inheritance (`I`).
+    Inheritance,
+    /// Clinical course (`C`), e.g. the onset of the disease.
+    ClinicalCourse,
+    /// Clinical modifier (`M`).
+    ClinicalModifier,
+    /// Past medical history (`H`).
+    PastMedicalHistory,
+}
+
+impl FromStr for Aspect {
+    type Err = PhenotypesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "P" => Ok(Aspect::PhenotypicAbnormality),
+            "I" => Ok(Aspect::Inheritance),
+            "C" => Ok(Aspect::ClinicalCourse),
+            "M" => Ok(Aspect::ClinicalModifier),
+            "H" => Ok(Aspect::PastMedicalHistory),
+            _ => Err(PhenotypesError::InvalidValue {
+                value: s.to_string(),
+                expected: "one of `P`, `I`, `C`, `M`, or `H`".to_string(),
+            }),
+        }
+    }
+}
+
+/// A record of the curator who created or revised the annotation, e.g. `HPO:skoehler[2017-07-13]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Biocuration {
+    curator: String,
+    date: Option<String>,
+}
+
+impl Biocuration {
+    /// Get the curator, e.g. `HPO:skoehler`.
+    pub fn curator(&self) -> &str {
+        &self.curator
+    }
+
+    /// Get the date of the curation in the `YYYY-MM-DD` format, e.g. `2017-07-13`.
+    pub fn date(&self) -> Option<&str> {
+        self.date.as_deref()
+    }
+}
+
+impl FromStr for Biocuration {
+    type Err = PhenotypesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('[') {
+            Some((curator, date)) => match date.strip_suffix(']') {
+                Some(date) => Ok(Biocuration {
+                    curator: curator.to_string(),
+                    date: Some(date.to_string()),
+                }),
+                None => Err(PhenotypesError::InvalidValue {
+                    value: s.to_string(),
+                    expected: "`curator[YYYY-MM-DD]`".to_string(),
+                }),
+            },
+            None => Ok(Biocuration {
+                curator: s.to_string(),
+                date: None,
+            }),
+        }
+    }
+}
+
+/// A single line of the `phenotype.hpoa` file.
+///
+/// The record is [`Identified`] by the ID of the annotated HPO term
+/// and it is [`Observable`] as excluded if negated by the `NOT` qualifier
+/// or if the frequency is zero, as unknown if the frequency is `0/0`,
+/// and as present otherwise.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HpoaRecord {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
+    disease_id: TermId,
+    disease_name: String,
+    is_negated: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
+    hpo_id: TermId,
+    references: Vec<String>,
+    evidence: EvidenceCode,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie::option"))]
+    onset: Option<TermId>,
+    frequency: Option<Frequency>,
+    sex: Option<Sex>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie::vec"))]
+    modifiers: Vec<TermId>,
+    aspect: Aspect,
+    biocuration: Vec<Biocuration>,
+}
+
+impl HpoaRecord {
+    /// Get the ID of the disease, e.g. `OMIM:619340`.
+    pub fn disease_id(&self) -> &TermId {
+        &self.disease_id
+    }
+
+    /// Get the name of the disease.
+    pub fn disease_name(&self) -> &str {
+        &self.disease_name
+    }
+
+    /// Test if the annotation is negated by the `NOT` qualifier.
+    pub fn is_negated(&self) -> bool {
+        self.is_negated
+    }
+
+    /// Get the ID of the annotated HPO term.
+    pub fn hpo_id(&self) -> &TermId {
+        &self.hpo_id
+    }
+
+    /// Get the references supporting the annotation, e.g. `PMID:31675180`.
+    pub fn references(&self) -> &[String] {
+        &self.references
+    }
+
+    /// Get the evidence code of the annotation.
+    pub fn evidence(&self) -> EvidenceCode {
+        self.evidence
+    }
+
+    /// Get the ID of the HPO term describing the onset of the feature.
+    pub fn onset(&self) -> Option<&TermId> {
+        self.onset.as_ref()
+    }
+
+    /// Get the frequency of the feature.
+    pub fn frequency(&self) -> Option<&Frequency> {
+        self.frequency.as_ref()
+    }
+
+    /// Get the sex the annotation is specific to.
+    pub fn sex(&self) -> Option<Sex> {
+        self.sex
+    }
+
+    /// Get the IDs of the clinical modifier terms.
+    pub fn modifiers(&self) -> &[TermId] {
+        &self.modifiers
+    }
+
+    /// Get the HPO sub-ontology of the annotated term.
+    pub fn aspect(&self) -> Aspect {
+        self.aspect
+    }
+
+    /// Get the curation records of the annotation.
+    pub fn biocuration(&self) -> &[Biocuration] {
+        &self.biocuration
+    }
+}
+
+impl Identified for HpoaRecord {
+    fn identifier(&self) -> &TermId {
+        &self.hpo_id
+    }
+}
+
+/// The probability of a negated record is `0`.
+impl FrequencyAware for HpoaRecord {
+    fn probability(&self) -> Option<f64> {
+        if self.is_negated {
+            Some(0.)
+        } else {
+            self.frequency.as_ref().and_then(Frequency::probability)
+        }
+    }
+}
+
+/// The record that is not negated but has the `0/0` frequency is unknown,
+/// in line with its undefined [`FrequencyAware::probability`].
+///
+/// ```
+/// use phenotypes::{FrequencyAware, Observable, ObservationState};
+/// use phenotypes::hpoa::HpoaRecord;
+///
+/// let line = "OMIM:619340\tDEE 96\t\tHP:0011097\tPMID:31675180\tPCS\t\t0/0\t\t\tP\tHPO:probinson[2021-06-21]";
+/// let record: HpoaRecord = line.parse().unwrap();
+///
+/// assert_eq!(record.observation_state(), ObservationState::Unknown);
+/// assert_eq!(record.probability(), None);
+///
+/// let negated: HpoaRecord = line.replacen("\t\tHP:0011097", "\tNOT\tHP:0011097", 1).parse().unwrap();
+/// assert_eq!(negated.observation_state(), ObservationState::Excluded);
+/// ```
+impl Observable for HpoaRecord {
+    fn observation_state(&self) -> ObservationState {
+        if self.is_negated {
+            return ObservationState::Excluded;
+        }
+        match &self.frequency {
+            Some(Frequency::Fraction(fraction)) if fraction.m() == 0 => ObservationState::Unknown,
+            Some(Frequency::Fraction(fraction)) if fraction.n() == 0 => ObservationState::Excluded,
+            Some(Frequency::Category(FrequencyCategory::Excluded)) => ObservationState::Excluded,
+            Some(Frequency::Percentage(percentage)) if *percentage == 0. => {
+                ObservationState::Excluded
+            }
+            _ => ObservationState::Present,
+        }
+    }
+}
+
+/// Parse the record from a tab-separated line of the annotation file.
+///
+/// ```
+/// use phenotypes::hpoa::{Aspect, EvidenceCode, HpoaRecord};
+///
+/// let line = "OMIM:619340\tDEE 96\t\tHP:0011097\tPMID:31675180\tPCS\tHP:0003593\t1/2\tMALE\tHP:0012828\tP\tHPO:probinson[2021-06-21]";
+/// let record: HpoaRecord = line.parse().unwrap();
+///
+/// assert_eq!(record.hpo_id().to_string(), "HP:0011097");
+/// assert_eq!(record.evidence(), EvidenceCode::PublishedClinicalStudy);
+/// assert_eq!(record.aspect(), Aspect::PhenotypicAbnormality);
+/// assert_eq!(record.biocuration()[0].date(), Some("2021-06-21"));
+/// ```
+///
+/// A percentage frequency is kept as a percentage, since the annotation
+/// does not report the size of the cohort:
+///
+/// ```
+/// use phenotypes::{Fraction, Frequency, FrequencyAware};
+/// use phenotypes::hpoa::HpoaRecord;
+///
+/// let line = "OMIM:619340\tDEE 96\t\tHP:0011097\tPMID:31675180\tPCS\t\t17%\t\t\tP\tHPO:probinson[2021-06-21]";
+/// let record: HpoaRecord = line.parse().unwrap();
+///
+/// assert_eq!(record.frequency(), Some(&Frequency::Percentage(17.)));
+/// assert_ne!(record.frequency(), Some(&Frequency::Fraction(Fraction::try_from((17u32, 100)).unwrap())));
+/// assert_eq!(record.probability(), Some(0.17));
+///
+/// let line = line.replace("17%", "170%");
+/// assert!(line.parse::<HpoaRecord>().is_err());
+/// ```
+impl FromStr for HpoaRecord {
+    type Err = PhenotypesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let columns: Vec<_> = s.trim_end_matches(['\r', '\n']).split('\t').collect();
+        if columns.len() != N_COLUMNS {
+            return Err(PhenotypesError::InvalidValue {
+                value: s.to_string(),
+                expected: format!("{N_COLUMNS} tab-separated columns"),
+            });
+        }
+
+        Ok(HpoaRecord {
+            disease_id: parse_term_id(columns[0])?,
+            disease_name: columns[1].to_string(),
+            is_negated: match columns[2] {
+                "" => false,
+                "NOT" => true,
+                other => {
+                    return Err(PhenotypesError::InvalidValue {
+                        value: other.to_string(),
+                        expected: "an empty qualifier or `NOT`".to_string(),
+                    });
+                }
+            },
+            hpo_id: parse_term_id(columns[3])?,
+            references: split_values(columns[4]).map(ToString::to_string).collect(),
+            evidence: columns[5].parse()?,
+            onset: non_empty(columns[6]).map(parse_term_id).transpose()?,
+            frequency: non_empty(columns[7]).map(parse_frequency).transpose()?,
+            sex: non_empty(columns[8]).map(parse_sex).transpose()?,
+            modifiers: split_values(columns[9])
+                .map(parse_term_id)
+                .collect::<Result<_, _>>()?,
+            aspect: columns[10].parse()?,
+            biocuration: split_values(columns[11])
+                .map(str::parse)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+fn non_empty(value: &str) -> Option<&str> {
+    if value.is_empty() { None } else { Some(value) }
+}
+
+fn split_values(value: &str) -> impl Iterator<Item = &str> {
+    value.split(';').map(str::trim).filter(|v| !v.is_empty())
+}
+
+fn parse_term_id(value: &str) -> Result<TermId, PhenotypesError> {
+    value.parse().map_err(|_| PhenotypesError::InvalidValue {
+        value: value.to_string(),
+        expected: "a CURIE".to_string(),
+    })
+}
+
+/// Parse the frequency column, keeping the percentages as percentages
+/// rather than making up an *n* of *100* cohort.
+fn parse_frequency(value: &str) -> Result<Frequency, PhenotypesError> {
+    if value.starts_with("HP:") {
+        let term_id = parse_term_id(value)?;
+        FrequencyCategory::try_from(&term_id).map(Frequency::Category)
+    } else if let Some(percentage) = value.strip_suffix('%') {
+        match percentage.trim().parse::<f64>() {
+            Ok(percentage) if (0. ..=100.).contains(&percentage) => {
+                Ok(Frequency::Percentage(percentage))
+            }
+            _ => Err(PhenotypesError::InvalidValue {
+                value: value.to_string(),
+                expected: "a percentage in the `[0, 100]` range".to_string(),
+            }),
+        }
+    } else {
+        Fraction::parse_with_policy(value, PercentagePolicy::Reject).map(Frequency::Fraction)
+    }
+}
+
+fn parse_sex(value: &str) -> Result<Sex, PhenotypesError> {
+    if value.eq_ignore_ascii_case("female") {
+        Ok(Sex::Female)
+    } else if value.eq_ignore_ascii_case("male") {
+        Ok(Sex::Male)
+    } else {
+        Err(PhenotypesError::InvalidValue {
+            value: value.to_string(),
+            expected: "`MALE` or `FEMALE`".to_string(),
+        })
+    }
+}
+
+/// A streaming reader of the [`HpoaRecord`]s.
+///
+/// The reader skips the comment lines that start with `#` and the header line,
+/// and reports the 1-based line number of the malformed rows and of the I/O errors.
+///
+/// ```
+/// use phenotypes::PhenotypesError;
+/// use phenotypes::hpoa::HpoaReader;
+///
+/// let hpoa = "\
+/// #version: 2024-04-26
+/// OMIM:619340\tDEE 96\t\tHP:0011097\tPMID:31675180\tPCS\t\t3/2\t\t\tP\tHPO:probinson[2021-06-21]
+/// ";
+///
+/// let err = HpoaReader::new(hpoa.as_bytes()).next().unwrap().unwrap_err();
+///
+/// assert!(matches!(err, PhenotypesError::MalformedRecord { line: 2, .. }));
+/// ```
+pub struct HpoaReader<R> {
+    read: R,
+    line: String,
+    line_number: usize,
+}
+
+impl<R> HpoaReader<R>
+where
+    R: BufRead,
+{
+    /// Create the reader from a buffered reader of the annotation file.
+    pub fn new(read: R) -> Self {
+        Self {
+            read,
+            line: String::new(),
+            line_number: 0,
+        }
+    }
+
+    /// Group the records of consecutive lines into per-disease [`DiseaseAnnotations`].
+    pub fn diseases(self) -> DiseaseAnnotationsIter<R> {
+        DiseaseAnnotationsIter {
+            records: self,
+            current: None,
+        }
+    }
+}
+
+impl<R> Iterator for HpoaReader<R>
+where
+    R: BufRead,
+{
+    type Item = Result<HpoaRecord, PhenotypesError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            self.line_number += 1;
+            match self.read.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {
+                    let line = self.line.trim_end_matches(['\r', '\n']);
+                    if line.is_empty() || line.starts_with('#') || line.starts_with("database_id") {
+                        continue;
+                    }
+                    return Some(line.parse().map_err(|e: PhenotypesError| {
+                        PhenotypesError::MalformedRecord {
+                            line: self.line_number,
+                            reason: e.to_string(),
+                        }
+                    }));
+                }
+                Err(e) => {
+                    return Some(Err(PhenotypesError::Io {
+                        line: self.line_number,
+                        source: Arc::new(e),
+                    }));
+                }
+            }
+        }
+    }
+}
+
+/// The annotations of a single disease.
+///
+/// The annotations are [`ObservableFeatures`] of the disease.
+/// However, only the records of the [`Aspect::PhenotypicAbnormality`]
+/// are considered to be the features.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiseaseAnnotations {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
+    disease_id: TermId,
+    disease_name: String,
+    records: Vec<HpoaRecord>,
+}
+
+impl DiseaseAnnotations {
+    /// Get the ID of the disease.
+    pub fn disease_id(&self) -> &TermId {
+        &self.disease_id
+    }
+
+    /// Get the name of the disease.
+    pub fn disease_name(&self) -> &str {
+        &self.disease_name
+    }
+
+    /// Get all annotation records of the disease.
+    pub fn records(&self) -> &[HpoaRecord] {
+        &self.records
+    }
+
+    /// Get an iterator over records of the `aspect`.
+    pub fn records_with_aspect(&self, aspect: Aspect) -> impl Iterator<Item = &HpoaRecord> {
+        self.records.iter().filter(move |r| r.aspect == aspect)
+    }
+}
+
+impl Identified for DiseaseAnnotations {
+    fn identifier(&self) -> &TermId {
+        &self.disease_id
+    }
+}
+
+impl ObservableFeatures for DiseaseAnnotations {
+    type Feature = HpoaRecord;
+
+    fn present_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        self.records_with_aspect(Aspect::PhenotypicAbnormality)
+            .filter(|r| r.is_present())
+    }
+
+    fn excluded_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        self.records_with_aspect(Aspect::PhenotypicAbnormality)
+            .filter(|r| r.is_excluded())
+    }
+
+    fn unknown_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        self.records_with_aspect(Aspect::PhenotypicAbnormality)
+            .filter(|r| r.is_unknown())
+    }
+}
+
+/// An iterator over [`DiseaseAnnotations`] created by [`HpoaReader::diseases`].
+///
+/// The iterator groups the records of consecutive lines with the same disease ID.
+///
+/// A malformed line is reported as an error in its place and the grouping goes on,
+/// hence the disease keeps the records that precede and follow the malformed line.
+///
+/// ```
+/// use phenotypes::PhenotypesError;
+/// use phenotypes::hpoa::HpoaReader;
+///
+/// let hpoa = "\
+/// OMIM:619340\tDEE 96\t\tHP:0011097\tPMID:31675180\tPCS\t\t1/2\t\t\tP\tHPO:probinson[2021-06-21]
+/// OMIM:619340\tDEE 96\t\tHP:0001250\tPMID:31675180\tPCS\t\t3/2\t\t\tP\tHPO:probinson[2021-06-21]
+/// OMIM:619340\tDEE 96\t\tHP:0001249\tPMID:31675180\tPCS\t\t1/1\t\t\tP\tHPO:probinson[2021-06-21]
+/// ORPHA:1899\tAMC\t\tHP:0002804\tORPHA:1899\tTAS\t\tHP:0040280\t\t\tP\tORPHA:orphadata[2024-04-26]
+/// ";
+///
+/// let mut diseases = HpoaReader::new(hpoa.as_bytes()).diseases();
+///
+/// let err = diseases.next().unwrap().unwrap_err();
+/// assert!(matches!(err, PhenotypesError::MalformedRecord { line: 2, .. }));
+///
+/// let dee96 = diseases.next().unwrap().unwrap();
+/// assert_eq!(dee96.disease_id().to_string(), "OMIM:619340");
+/// assert_eq!(dee96.records().len(), 2);
+///
+/// let amc = diseases.next().unwrap().unwrap();
+/// assert_eq!(amc.disease_id().to_string(), "ORPHA:1899");
+/// assert!(diseases.next().is_none());
+/// ```
+pub struct DiseaseAnnotationsIter<R> {
+    records: HpoaReader<R>,
+    current: Option<DiseaseAnnotations>,
+}
+
+impl<R> Iterator for DiseaseAnnotationsIter<R>
+where
+    R: BufRead,
+{
+    type Item = Result<DiseaseAnnotations, PhenotypesError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let record = match self.records.next() {
+                Some(Ok(record)) => record,
+                Some(Err(e)) => return Some(Err(e)),
+                None => return self.current.take().map(Ok),
+            };
+            match &mut self.current {
+                Some(current) if current.disease_id == record.disease_id => {
+                    current.records.push(record);
+                }
+                _ => {
+                    let next = DiseaseAnnotations {
+                        disease_id: record.disease_id.clone(),
+                        disease_name: record.disease_name.clone(),
+                        records: vec![record],
+                    };
+                    if let Some(done) = self.current.replace(next) {
+                        return Some(Ok(done));
+                    }
+                }
+            }
+        }
+    }
+}
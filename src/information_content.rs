@@ -0,0 +1,194 @@
+//! A module for computing the information content (IC) of ontology terms
+//! from a corpus of annotated items.
+//!
+//! The IC of a term `t` is `-ln(p(t))`, where `p(t)` is the fraction of the corpus items
+//! annotated with `t`. Due to the true path rule, an item annotated with a term
+//! is implicitly annotated with all its ancestors, hence the counts are propagated
+//! up the ontology hierarchy.
+//!
+//! ## Examples
+//!
+//! ```
+//! # let hpo = include!("../tests/common/toy_hpo.rs");
+//! use ontolius::TermId;
+//! use phenotypes::FrequencyCategory;
+//! use phenotypes::information_content::{InformationContent, UnseenTerms};
+//! use phenotypes::simple::SimplePhenotypicFeature;
+//!
+//! let feature = |curie: &str, category| SimplePhenotypicFeature::from_frequency_category(
+//!     curie.parse().unwrap(),
+//!     category,
+//! );
+//!
+//! let diseases = vec![
+//!     vec![feature("HP:0100259", FrequencyCategory::Frequent)],
+//!     vec![feature("HP:0100258", FrequencyCategory::Obligate)],
+//!     vec![
+//!         feature("HP:0010442", FrequencyCategory::VeryFrequent),
+//!         feature("HP:0001250", FrequencyCategory::Excluded),
+//!     ],
+//!     vec![feature("HP:0001250", FrequencyCategory::Occasional)],
+//! ];
+//!
+//! // `hpo` is a small hand-built ontology with the HPO terms used in the example.
+//! let ic = InformationContent::builder()
+//!     .unseen_terms(UnseenTerms::Maximum)
+//!     .build(&diseases, &hpo);
+//!
+//! let polydactyly: TermId = "HP:0010442".parse().unwrap();
+//! let seizure: TermId = "HP:0001250".parse().unwrap();
+//! let all: TermId = "HP:0000001".parse().unwrap();
+//!
+//! // 3 of 4 diseases have polydactyly ...
+//! assert_eq!(ic.ic(&polydactyly), -(3f64 / 4.).ln());
+//! // ... and 1 of 4 have seizures, since excluded annotations are ignored by default.
+//! assert_eq!(ic.ic(&seizure), -(1f64 / 4.).ln());
+//! // All diseases are annotated with the root term.
+//! assert_eq!(ic.ic(&all), 0.);
+//! // The IC of an unseen term is the IC of a term annotated in a single item.
+//! let unseen: TermId = "HP:0000707".parse().unwrap();
+//! assert_eq!(ic.ic(&unseen), 4f64.ln());
+//! ```
+use std::collections::{HashMap, HashSet};
+
+use ontolius::ontology::HierarchyWalks;
+use ontolius::{Identified, TermId};
+
+use crate::ObservableFeatures;
+use crate::similarity::TermInformationContent;
+
+/// The treatment of the excluded annotations when counting the annotated items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExcludedAnnotations {
+    /// Count only the present features.
+    #[default]
+    Ignore,
+    /// Count the excluded features as if they were annotated,
+    /// e.g. if the exclusion indicates that the feature is relevant for the item.
+    Count,
+}
+
+/// The treatment of the terms that were not annotated in any item of the corpus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnseenTerms {
+    /// The IC of an unseen term is `0`, as if the term was uninformative.
+    #[default]
+    Zero,
+    /// The IC of an unseen term is the IC of a term annotated in a single item,
+    /// i.e. the maximum IC of the corpus.
+    Maximum,
+}
+
+/// A builder for configuring the computation of the [`InformationContent`].
+#[derive(Debug, Clone, Default)]
+pub struct InformationContentBuilder {
+    excluded: ExcludedAnnotations,
+    unseen: UnseenTerms,
+}
+
+impl InformationContentBuilder {
+    /// Set the treatment of the excluded annotations.
+    #[must_use]
+    pub fn excluded_annotations(mut self, excluded: ExcludedAnnotations) -> Self {
+        self.excluded = excluded;
+        self
+    }
+
+    /// Set the treatment of the unseen terms.
+    #[must_use]
+    pub fn unseen_terms(mut self, unseen: UnseenTerms) -> Self {
+        self.unseen = unseen;
+        self
+    }
+
+    /// Compute the information content from the `corpus` items
+    /// and the ontology `hierarchy`.
+    ///
+    /// The items with no counted annotations are not considered to be a part of the corpus.
+    pub fn build<'a, I, S, O>(self, corpus: I, hierarchy: &O) -> InformationContent
+    where
+        I: IntoIterator<Item = &'a S>,
+        S: ObservableFeatures + 'a,
+        S::Feature: Identified,
+        O: HierarchyWalks,
+    {
+        let mut counts: HashMap<TermId, usize> = HashMap::new();
+        let mut n_items = 0;
+        for item in corpus {
+            let excluded = match self.excluded {
+                ExcludedAnnotations::Ignore => None,
+                ExcludedAnnotations::Count => Some(item.excluded_features()),
+            };
+            let closure: HashSet<_> = item
+                .present_features()
+                .chain(excluded.into_iter().flatten())
+                .flat_map(|feature| hierarchy.iter_term_and_ancestor_ids(feature.identifier()))
+                .collect();
+            if closure.is_empty() {
+                continue;
+            }
+            n_items += 1;
+            for term_id in closure {
+                *counts.entry(term_id.clone()).or_default() += 1;
+            }
+        }
+
+        let values = counts
+            .into_iter()
+            .map(|(term_id, count)| (term_id, -(count as f64 / n_items as f64).ln()))
+            .collect();
+        let unseen = match self.unseen {
+            UnseenTerms::Zero => 0.,
+            UnseenTerms::Maximum if n_items == 0 => 0.,
+            UnseenTerms::Maximum => (n_items as f64).ln(),
+        };
+
+        InformationContent {
+            values,
+            n_items,
+            unseen,
+        }
+    }
+}
+
+/// The information content (IC) of ontology terms computed from a corpus of annotated items.
+///
+/// Use [`InformationContent::builder`] to configure the computation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InformationContent {
+    values: HashMap<TermId, f64>,
+    n_items: usize,
+    unseen: f64,
+}
+
+impl InformationContent {
+    /// Create a new builder with the default configuration.
+    pub fn builder() -> InformationContentBuilder {
+        InformationContentBuilder::default()
+    }
+
+    /// Get the IC of the term.
+    ///
+    /// The IC of a term not annotated in any item is determined by [`UnseenTerms`].
+    pub fn ic(&self, term_id: &TermId) -> f64 {
+        self.get(term_id).unwrap_or(self.unseen)
+    }
+
+    /// Get the IC of the term or `None` if the term was not annotated in any item.
+    pub fn get(&self, term_id: &TermId) -> Option<f64> {
+        self.values.get(term_id).copied()
+    }
+
+    /// Get the number of corpus items with at least one counted annotation.
+    pub fn item_count(&self) -> usize {
+        self.n_items
+    }
+}
+
+impl TermInformationContent for InformationContent {
+    fn information_content(&self, term_id: &TermId) -> Option<f64> {
+        Some(self.ic(term_id))
+    }
+}
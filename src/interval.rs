@@ -0,0 +1,225 @@
+use crate::stats::{beta_quantile, normal_quantile};
+use crate::{Count, Fraction};
+
+/// A two-sided `ConfidenceInterval` of an estimate, such as a proportion or an odds ratio.
+///
+/// The `confidence_level` is the nominal coverage of the interval, e.g. `0.95`.
+/// The bounds of a proportion interval are in the `[0, 1]` range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfidenceInterval {
+    lower: f64,
+    upper: f64,
+    confidence_level: f64,
+}
+
+impl ConfidenceInterval {
+    pub(crate) fn new(lower: f64, upper: f64, confidence_level: f64) -> Self {
+        Self {
+            lower,
+            upper,
+            confidence_level,
+        }
+    }
+
+    /// Get the lower bound of the interval.
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+
+    /// Get the upper bound of the interval.
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    /// Get the nominal coverage of the interval.
+    pub fn confidence_level(&self) -> f64 {
+        self.confidence_level
+    }
+
+    /// Get the width of the interval.
+    pub fn width(&self) -> f64 {
+        self.upper - self.lower
+    }
+
+    /// Test if the interval includes the `value`.
+    pub fn contains(&self, value: f64) -> bool {
+        self.lower <= value && value <= self.upper
+    }
+}
+
+/// Binomial confidence intervals of the *n* of *m* proportion.
+///
+/// All methods return the whole `[0, 1]` interval for the `0/0` fraction,
+/// since no items were investigated and the data do not constrain the proportion.
+///
+/// ## Examples
+///
+/// ```
+/// use phenotypes::Fraction;
+///
+/// let f = Fraction::try_from((7u32, 10)).unwrap();
+///
+/// let ci = f.wilson_interval(0.95);
+/// assert!((ci.lower() - 0.3968).abs() < 1e-4);
+/// assert!((ci.upper() - 0.8922).abs() < 1e-4);
+/// assert_eq!(ci.confidence_level(), 0.95);
+///
+/// let empty = Fraction::try_from((0u32, 0)).unwrap();
+/// for ci in [
+///     empty.wilson_interval(0.95),
+///     empty.clopper_pearson_interval(0.95),
+///     empty.agresti_coull_interval(0.95),
+///     empty.jeffreys_interval(0.95),
+/// ] {
+///     assert_eq!((ci.lower(), ci.upper()), (0., 1.));
+///     assert_eq!(ci.confidence_level(), 0.95);
+/// }
+/// ```
+impl<T> Fraction<T>
+where
+    T: Count,
+{
+    /// Compute the Wilson score interval.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the `confidence_level` is not in the open interval `(0, 1)`.
+    pub fn wilson_interval(&self, confidence_level: f64) -> ConfidenceInterval {
+        let z = critical_value(confidence_level);
+        let Some((n, m)) = self.counts() else {
+            return make_interval(0., 1., confidence_level);
+        };
+        let p = n / m;
+        let z2 = z * z;
+        let denominator = 1. + z2 / m;
+        let center = (p + z2 / (2. * m)) / denominator;
+        let half_width = z * (p * (1. - p) / m + z2 / (4. * m * m)).sqrt() / denominator;
+
+        make_interval(center - half_width, center + half_width, confidence_level)
+    }
+
+    /// Compute the Clopper–Pearson "exact" interval.
+    ///
+    /// ```
+    /// use phenotypes::Fraction;
+    ///
+    /// let f = Fraction::try_from((7u32, 10)).unwrap();
+    ///
+    /// let ci = f.clopper_pearson_interval(0.95);
+    /// assert!((ci.lower() - 0.3475).abs() < 1e-4);
+    /// assert!((ci.upper() - 0.9333).abs() < 1e-4);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the `confidence_level` is not in the open interval `(0, 1)`.
+    pub fn clopper_pearson_interval(&self, confidence_level: f64) -> ConfidenceInterval {
+        let alpha = alpha(confidence_level);
+        let Some((n, m)) = self.counts() else {
+            return make_interval(0., 1., confidence_level);
+        };
+        let lower = if n == 0. {
+            0.
+        } else {
+            beta_quantile(alpha / 2., n, m - n + 1.)
+        };
+        let upper = if n == m {
+            1.
+        } else {
+            beta_quantile(1. - alpha / 2., n + 1., m - n)
+        };
+
+        make_interval(lower, upper, confidence_level)
+    }
+
+    /// Compute the Agresti–Coull interval.
+    ///
+    /// ```
+    /// use phenotypes::Fraction;
+    ///
+    /// let f = Fraction::try_from((0u32, 10)).unwrap();
+    ///
+    /// let ci = f.agresti_coull_interval(0.95);
+    /// assert_eq!(ci.lower(), 0.);
+    /// assert!((ci.upper() - 0.3209).abs() < 1e-4);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the `confidence_level` is not in the open interval `(0, 1)`.
+    pub fn agresti_coull_interval(&self, confidence_level: f64) -> ConfidenceInterval {
+        let z = critical_value(confidence_level);
+        let Some((n, m)) = self.counts() else {
+            return make_interval(0., 1., confidence_level);
+        };
+        let z2 = z * z;
+        let m_adj = m + z2;
+        let p_adj = (n + z2 / 2.) / m_adj;
+        let half_width = z * (p_adj * (1. - p_adj) / m_adj).sqrt();
+
+        make_interval(p_adj - half_width, p_adj + half_width, confidence_level)
+    }
+
+    /// Compute the Jeffreys interval,
+    /// the equal-tailed credible interval under the Beta(1/2, 1/2) prior.
+    ///
+    /// ```
+    /// use phenotypes::Fraction;
+    ///
+    /// let f = Fraction::try_from((7u32, 10)).unwrap();
+    ///
+    /// let ci = f.jeffreys_interval(0.95);
+    /// assert!((ci.lower() - 0.3942).abs() < 1e-4);
+    /// assert!((ci.upper() - 0.9073).abs() < 1e-4);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the `confidence_level` is not in the open interval `(0, 1)`.
+    pub fn jeffreys_interval(&self, confidence_level: f64) -> ConfidenceInterval {
+        let alpha = alpha(confidence_level);
+        let Some((n, m)) = self.counts() else {
+            return make_interval(0., 1., confidence_level);
+        };
+        let lower = if n == 0. {
+            0.
+        } else {
+            beta_quantile(alpha / 2., n + 0.5, m - n + 0.5)
+        };
+        let upper = if n == m {
+            1.
+        } else {
+            beta_quantile(1. - alpha / 2., n + 0.5, m - n + 0.5)
+        };
+
+        make_interval(lower, upper, confidence_level)
+    }
+
+    /// Get the counts as floats or `None` if the denominator is zero.
+    fn counts(&self) -> Option<(f64, f64)> {
+        let m: f64 = self.m().to_f64();
+        if m == 0. {
+            None
+        } else {
+            Some((self.n().to_f64(), m))
+        }
+    }
+}
+
+fn alpha(confidence_level: f64) -> f64 {
+    assert!(
+        confidence_level > 0. && confidence_level < 1.,
+        "Confidence level must be in (0, 1) but was {confidence_level}"
+    );
+    1. - confidence_level
+}
+
+/// Get the two-sided critical value of the standard normal distribution.
+pub(crate) fn critical_value(confidence_level: f64) -> f64 {
+    normal_quantile(1. - alpha(confidence_level) / 2.)
+}
+
+fn make_interval(lower: f64, upper: f64, confidence_level: f64) -> ConfidenceInterval {
+    ConfidenceInterval::new(lower.clamp(0., 1.), upper.clamp(0., 1.), confidence_level)
+}
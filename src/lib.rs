@@ -1,9 +1,34 @@
 #![doc = include_str!("../README.md")]
 #![deny(unsafe_code)] // at least for now.. 👻
 
+pub mod association;
+pub mod bayes;
+pub mod cohort;
+mod count;
+pub mod diagnosis;
+mod error;
+mod frequency;
+pub mod hpoa;
+pub mod information_content;
+mod interval;
 mod model;
+pub mod modifiers;
 mod observation;
+#[cfg(feature = "phenopackets")]
+pub mod phenopackets;
+pub mod propagation;
+pub mod provenance;
+#[cfg(feature = "serde")]
+mod serde_curie;
+pub mod similarity;
 pub mod simple;
+mod stats;
+pub mod temporal;
+pub mod validation;
 
-pub use model::Fraction;
-pub use observation::{Observable, ObservableFeatures};
+pub use count::{Count, RatioFloat};
+pub use error::PhenotypesError;
+pub use frequency::{Frequency, FrequencyAware, FrequencyCategory};
+pub use interval::ConfidenceInterval;
+pub use model::{Fraction, FractionPart, PercentagePolicy, Sex};
+pub use observation::{Observable, ObservableFeatures, ObservationState};
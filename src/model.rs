@@ -1,4 +1,10 @@
-use std::ops::Add;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+use std::str::FromStr;
+
+use crate::{Count, PhenotypesError, RatioFloat};
 
 /// A `Fraction` represents the *n* of *m* frequency of a feature in one or more annotated items.
 ///
@@ -11,9 +17,75 @@ use std::ops::Add;
 /// The `numerator` must be less than or equal to `denominator`.
 /// However, it is possible for both to equal to `0`.
 ///
-/// `Fraction` is generic over the data type that represents the count.
+/// `Fraction` is generic over the [`Count`] type of the counts.
 /// To simplify the API, we use `u32` as default.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// ## Count types
+///
+/// A `Fraction` converts losslessly into a `Fraction` with a wider count type:
+///
+/// ```
+/// use phenotypes::Fraction;
+///
+/// let f = Fraction::try_from((3u16, 10)).unwrap();
+/// let g: Fraction<u64> = f.into();
+/// assert_eq!(g, Fraction::try_from((3u64, 10)).unwrap());
+/// ```
+///
+/// The conversion into a narrower count type fails if the denominator does not fit:
+///
+/// ```
+/// use phenotypes::{Fraction, PhenotypesError};
+///
+/// let f = Fraction::try_from((3u64, 10)).unwrap();
+/// assert_eq!(Fraction::<u16>::try_from(f), Ok(Fraction::try_from((3u16, 10)).unwrap()));
+///
+/// let f = Fraction::try_from((3u64, 70_000)).unwrap();
+/// assert_eq!(
+///     Fraction::<u16>::try_from(f),
+///     Err(PhenotypesError::CountOutOfRange { value: "70000".to_string() }),
+/// );
+/// ```
+///
+/// The counts must not be negative. Use [`Fraction::try_from_signed`]
+/// to create a `Fraction` from signed integers.
+///
+/// ## Serialization
+///
+/// With the `serde` feature, `Fraction` is (de)serialized as a struct with `n` and `m` fields.
+/// The deserialization fails if the numerator is greater than the denominator.
+///
+/// ```
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use phenotypes::Fraction;
+///
+/// let f = Fraction::try_from((3u32, 10)).unwrap();
+///
+/// let json = serde_json::to_string(&f).unwrap();
+/// assert_eq!(json, r#"{"n":3,"m":10}"#);
+///
+/// let g: Fraction = serde_json::from_str(&json).unwrap();
+/// assert_eq!(f, g);
+///
+/// assert!(serde_json::from_str::<Fraction>(r#"{"n":5,"m":3}"#).is_err());
+/// # }
+/// ```
+///
+/// ## Equality and ordering
+///
+/// The equality and the hash of `Fraction` are structural, hence `1/2` and `2/4` are different
+/// fractions, as they represent different cohorts. Use [`Fraction::cmp_by_ratio`]
+/// or [`Fraction::by_ratio`] to compare the fractions by their values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "FractionData<T>",
+        bound(deserialize = "T: serde::Deserialize<'de> + Count")
+    )
+)]
 pub struct Fraction<T = u32> {
     n: T,
     /// The `m` represents the total count of annotated items investigated
@@ -23,7 +95,7 @@ pub struct Fraction<T = u32> {
 
 impl<T> Fraction<T>
 where
-    T: Clone,
+    T: Count,
 {
     /// Get the value of the numerator.
     pub fn n(&self) -> T {
@@ -45,7 +117,7 @@ where
 /// ```
 /// use phenotypes::Fraction;
 ///
-/// let f = Fraction::try_from((1, 10)).expect("Should never fail for this input");
+/// let f = Fraction::try_from((1u32, 10)).expect("Should never fail for this input");
 ///
 /// assert_eq!(f.n(), 1);
 /// assert_eq!(f.m(), 10);
@@ -56,14 +128,14 @@ where
 /// ```
 /// use phenotypes::Fraction;
 ///
-/// let err = Fraction::try_from((5, 3)).unwrap_err();
-/// assert_eq!(err, "Numerator must be less than or equal to denominator!");
+/// let err = Fraction::try_from((5u32, 3)).unwrap_err();
+/// assert_eq!(err.to_string(), "numerator 5 must be less than or equal to denominator 3");
 /// ```
 impl<T> TryFrom<(T, T)> for Fraction<T>
 where
-    T: PartialOrd,
+    T: Count,
 {
-    type Error = &'static str;
+    type Error = PhenotypesError;
 
     fn try_from(value: (T, T)) -> Result<Self, Self::Error> {
         let (numerator, denominator) = value;
@@ -73,17 +145,40 @@ where
                 m: denominator,
             })
         } else {
-            Err("Numerator must be less than or equal to denominator!")
+            Err(PhenotypesError::NumeratorExceedsDenominator {
+                n: numerator.to_string(),
+                m: denominator.to_string(),
+            })
         }
     }
 }
 
+/// The unvalidated *n* and *m* counts used to deserialize the `Fraction`.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct FractionData<T> {
+    n: T,
+    m: T,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<FractionData<T>> for Fraction<T>
+where
+    T: Count,
+{
+    type Error = PhenotypesError;
+
+    fn try_from(value: FractionData<T>) -> Result<Self, Self::Error> {
+        Fraction::try_from((value.n, value.m))
+    }
+}
+
 /// Make a new `Fraction` by summing up *n* and *m* values.
 ///
 /// ```
 /// use phenotypes::Fraction;
 ///
-/// let a = Fraction::try_from((1, 2)).unwrap();
+/// let a = Fraction::try_from((1u32, 2)).unwrap();
 /// let b = Fraction::try_from((3, 3)).unwrap();
 ///
 /// let c = a + b;
@@ -91,9 +186,39 @@ where
 /// assert_eq!(c.n(), 4);
 /// assert_eq!(c.m(), 5);
 /// ```
+///
+/// The addition panics on overflow in debug builds and wraps in release builds,
+/// which can break the `n <= m` invariant.
+/// Use [`Fraction::checked_add`] or [`Fraction::saturating_add`] if the counts can be large.
+///
+/// The overflow-aware additions hold the `n <= m` invariant for all counts:
+///
+/// ```
+/// use phenotypes::Fraction;
+///
+/// let fractions: Vec<Fraction<u8>> = (0u8..=u8::MAX)
+///     .step_by(15)
+///     .flat_map(|m| (0..=m).step_by(7).map(move |n| Fraction::try_from((n, m)).unwrap()))
+///     .collect();
+///
+/// for a in &fractions {
+///     for b in &fractions {
+///         let s = a.saturating_add(b);
+///         assert!(s.n() <= s.m());
+///
+///         match a.checked_add(b) {
+///             Some(c) => {
+///                 assert!(c.n() <= c.m());
+///                 assert_eq!(c, s);
+///             }
+///             None => assert!(u16::from(a.m()) + u16::from(b.m()) > 255),
+///         }
+///     }
+/// }
+/// ```
 impl<T> Add<Self> for Fraction<T>
 where
-    T: Add<Output = T>,
+    T: Count,
 {
     type Output = Self;
 
@@ -104,3 +229,618 @@ where
         }
     }
 }
+
+/// Add the *n* and *m* values of another `Fraction` in place.
+///
+/// ```
+/// use phenotypes::Fraction;
+///
+/// let mut a = Fraction::try_from((1u32, 2)).unwrap();
+/// a += Fraction::try_from((3, 3)).unwrap();
+///
+/// assert_eq!(a, Fraction::try_from((4, 5)).unwrap());
+/// ```
+impl<T> AddAssign<Self> for Fraction<T>
+where
+    T: Count,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        self.n += rhs.n;
+        self.m += rhs.m;
+    }
+}
+
+/// Sum up the fractions, e.g. the fractions reported by several publications.
+///
+/// The sum of no fractions is `0/0`.
+///
+/// ```
+/// use phenotypes::Fraction;
+///
+/// let fractions: Vec<Fraction> = [(1, 2), (0, 3), (4, 4)]
+///     .into_iter()
+///     .map(|pair| Fraction::try_from(pair).unwrap())
+///     .collect();
+///
+/// let total: Fraction = fractions.iter().sum();
+/// assert_eq!(total, Fraction::try_from((5, 9)).unwrap());
+///
+/// let total: Fraction = fractions.into_iter().sum();
+/// assert_eq!(total, Fraction::try_from((5, 9)).unwrap());
+///
+/// let empty: Fraction = std::iter::empty::<Fraction>().sum();
+/// assert_eq!(empty, Fraction::try_from((0, 0)).unwrap());
+/// ```
+impl<T> Sum<Self> for Fraction<T>
+where
+    T: Count,
+{
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(
+            Fraction {
+                n: T::zero(),
+                m: T::zero(),
+            },
+            Add::add,
+        )
+    }
+}
+
+impl<'a, T> Sum<&'a Self> for Fraction<T>
+where
+    T: Count + 'a,
+{
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.cloned().sum()
+    }
+}
+
+impl<T> Fraction<T>
+where
+    T: Count,
+{
+    /// Get the ratio *n / m* in the float type `F`
+    /// or `None` if the denominator is zero.
+    ///
+    /// The counts are converted with [`Count::to_f64`],
+    /// hence the ratio of very large counts may lose precision.
+    ///
+    /// ```
+    /// use phenotypes::Fraction;
+    ///
+    /// let f = Fraction::try_from((3u32, 4)).unwrap();
+    /// assert_eq!(f.ratio::<f64>(), Some(0.75));
+    ///
+    /// let f = Fraction::try_from((1u16, 8)).unwrap();
+    /// assert_eq!(f.ratio::<f32>(), Some(0.125));
+    ///
+    /// let f = Fraction::try_from((1u64, 4)).unwrap();
+    /// assert_eq!(f.ratio::<f64>(), Some(0.25));
+    ///
+    /// let f = Fraction::try_from((1usize, 2)).unwrap();
+    /// assert_eq!(f.ratio::<f32>(), Some(0.5));
+    ///
+    /// # #[cfg(feature = "bigint")]
+    /// # {
+    /// use num_bigint::BigUint;
+    ///
+    /// let f = Fraction::try_from((BigUint::from(1u8), BigUint::from(u128::MAX))).unwrap();
+    /// assert!(f.ratio::<f64>().unwrap() > 0.);
+    /// # }
+    ///
+    /// let empty = Fraction::try_from((0u32, 0)).unwrap();
+    /// assert_eq!(empty.ratio::<f64>(), None);
+    /// ```
+    pub fn ratio<F>(&self) -> Option<F>
+    where
+        F: RatioFloat,
+    {
+        if self.m == T::zero() {
+            None
+        } else {
+            Some(F::from_f64(self.n.to_f64() / self.m.to_f64()))
+        }
+    }
+
+    /// Get the percentage *100 · n / m* in the float type `F`
+    /// or `None` if the denominator is zero.
+    ///
+    /// ```
+    /// use phenotypes::Fraction;
+    ///
+    /// let f = Fraction::try_from((3u32, 4)).unwrap();
+    /// assert_eq!(f.percentage::<f64>(), Some(75.));
+    ///
+    /// let f = Fraction::try_from((3u64, 4)).unwrap();
+    /// assert_eq!(f.percentage::<f32>(), Some(75.));
+    ///
+    /// let empty = Fraction::try_from((0u32, 0)).unwrap();
+    /// assert_eq!(empty.percentage::<f64>(), None);
+    /// ```
+    pub fn percentage<F>(&self) -> Option<F>
+    where
+        F: RatioFloat,
+    {
+        if self.m == T::zero() {
+            None
+        } else {
+            Some(F::from_f64(100. * self.n.to_f64() / self.m.to_f64()))
+        }
+    }
+
+    /// Get the complementary *m - n* of *m* fraction,
+    /// e.g. the number of items where the feature was excluded.
+    ///
+    /// ```
+    /// use phenotypes::Fraction;
+    ///
+    /// let f = Fraction::try_from((3u32, 10)).unwrap();
+    /// assert_eq!(f.complement(), Fraction::try_from((7, 10)).unwrap());
+    ///
+    /// // The complement holds the `n <= m` invariant.
+    /// for m in 0u8..=u8::MAX {
+    ///     for n in 0..=m {
+    ///         let c = Fraction::try_from((n, m)).unwrap().complement();
+    ///         assert!(c.n() <= c.m());
+    ///         assert_eq!(c.complement(), Fraction::try_from((n, m)).unwrap());
+    ///     }
+    /// }
+    /// ```
+    pub fn complement(&self) -> Self {
+        Fraction {
+            n: self.m() - self.n(),
+            m: self.m(),
+        }
+    }
+}
+
+impl<T> Fraction<T>
+where
+    T: Count,
+{
+    /// Compare the fractions by the ratio *n / m*.
+    ///
+    /// The ratios are compared by the Euclidean algorithm on their continued fraction expansions,
+    /// which needs neither cross-multiplication nor floating point numbers
+    /// and, therefore, it cannot overflow or lose precision.
+    ///
+    /// The `0/0` fraction has an undefined ratio. It is equal to other `0/0` fractions
+    /// and less than any other fraction, including `0/m`, so that the features
+    /// that were not investigated sort lowest.
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use phenotypes::Fraction;
+    ///
+    /// let f = |n: u64, m: u64| Fraction::try_from((n, m)).unwrap();
+    ///
+    /// assert_eq!(f(1, 2).cmp_by_ratio(&f(2, 4)), Ordering::Equal);
+    /// assert_eq!(f(1, 3).cmp_by_ratio(&f(1, 2)), Ordering::Less);
+    /// assert_eq!(f(0, 0).cmp_by_ratio(&f(0, 5)), Ordering::Less);
+    /// assert_eq!(f(0, 0).cmp_by_ratio(&f(0, 0)), Ordering::Equal);
+    ///
+    /// // No overflow even if the cross products exceed `u64::MAX`.
+    /// let max = u64::MAX;
+    /// assert_eq!(f(max - 2, max - 1).cmp_by_ratio(&f(max - 1, max)), Ordering::Less);
+    /// ```
+    ///
+    /// The comparison agrees with the cross-multiplication for all small fractions:
+    ///
+    /// ```
+    /// use phenotypes::Fraction;
+    ///
+    /// for (a, b) in (0u8..=24).flat_map(|a| (0u8..=24).map(move |b| (a, b))) {
+    ///     for (c, d) in (0u8..=24).flat_map(|c| (0u8..=24).map(move |d| (c, d))) {
+    ///         if a > b || c > d || b == 0 || d == 0 {
+    ///             continue;
+    ///         }
+    ///         let x = Fraction::try_from((a, b)).unwrap();
+    ///         let y = Fraction::try_from((c, d)).unwrap();
+    ///         let expected = (u16::from(a) * u16::from(d)).cmp(&(u16::from(c) * u16::from(b)));
+    ///         assert_eq!(x.cmp_by_ratio(&y), expected);
+    ///     }
+    /// }
+    /// ```
+    pub fn cmp_by_ratio(&self, other: &Self) -> Ordering {
+        let zero = T::zero();
+        match (self.m == zero, other.m == zero) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => cmp_ratios(self.n(), self.m(), other.n(), other.m()),
+        }
+    }
+
+    /// Compare the fractions by the ratio,
+    /// a convenience for sorting with [`slice::sort_by`] and friends.
+    ///
+    /// See [`Fraction::cmp_by_ratio`] for the details.
+    ///
+    /// ```
+    /// use phenotypes::Fraction;
+    ///
+    /// let mut fractions: Vec<Fraction> = [(2, 3), (0, 0), (1, 4), (2, 4), (1, 2)]
+    ///     .into_iter()
+    ///     .map(|pair| Fraction::try_from(pair).unwrap())
+    ///     .collect();
+    ///
+    /// fractions.sort_by(Fraction::by_ratio);
+    ///
+    /// let sorted: Vec<_> = fractions.iter().map(ToString::to_string).collect();
+    /// assert_eq!(sorted, ["0/0", "1/4", "2/4", "1/2", "2/3"]);
+    /// ```
+    pub fn by_ratio(a: &Self, b: &Self) -> Ordering {
+        a.cmp_by_ratio(b)
+    }
+}
+
+/// Compare `a / b` and `c / d`, where `b` and `d` are positive.
+fn cmp_ratios<T>(mut a: T, mut b: T, mut c: T, mut d: T) -> Ordering
+where
+    T: Count,
+{
+    // Each step compares the integer parts and continues with the reciprocals
+    // of the remainders, which flips the ordering.
+    let mut flipped = false;
+    loop {
+        let (q1, q2) = (a.clone() / b.clone(), c.clone() / d.clone());
+        if q1 != q2 {
+            let ordering = q1.cmp(&q2);
+            return if flipped {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+        }
+        let (r1, r2) = (a % b.clone(), c % d.clone());
+        let zero = T::zero();
+        let ordering = match (r1 == zero, r2 == zero) {
+            (true, true) => return Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => {
+                (a, b, c, d) = (b, r1, d, r2);
+                flipped = !flipped;
+                continue;
+            }
+        };
+        return if flipped {
+            ordering.reverse()
+        } else {
+            ordering
+        };
+    }
+}
+
+impl<T> Fraction<T>
+where
+    T: Count,
+{
+    /// Add the *n* and *m* values of the fractions,
+    /// returning `None` if the denominator overflows.
+    ///
+    /// The numerator cannot overflow if the denominator does not.
+    ///
+    /// ```
+    /// use phenotypes::Fraction;
+    ///
+    /// let a = Fraction::try_from((100u8, 200)).unwrap();
+    /// let b = Fraction::try_from((50u8, 50)).unwrap();
+    ///
+    /// assert_eq!(a.checked_add(&b), Some(Fraction::try_from((150, 250)).unwrap()));
+    /// assert_eq!(a.checked_add(&a), None);
+    /// ```
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(Fraction {
+            m: self.m.checked_add(&rhs.m)?,
+            n: self.n() + rhs.n(),
+        })
+    }
+
+    /// Add the *n* and *m* values of the fractions,
+    /// saturating each count at the maximum value of the type.
+    ///
+    /// The result holds the `n <= m` invariant, but it is not the exact sum
+    /// if the counts saturated.
+    ///
+    /// ```
+    /// use phenotypes::Fraction;
+    ///
+    /// let a = Fraction::try_from((100u8, 200)).unwrap();
+    ///
+    /// assert_eq!(a.saturating_add(&a), Fraction::try_from((200, 255)).unwrap());
+    /// ```
+    pub fn saturating_add(&self, rhs: &Self) -> Self {
+        Fraction {
+            n: self.n.saturating_add(&rhs.n),
+            m: self.m.saturating_add(&rhs.m),
+        }
+    }
+
+    /// Create a `Fraction` from signed counts, e.g. from the columns
+    /// of a table with signed integers.
+    ///
+    /// Returns an error if a count is negative, if it cannot be represented by `T`,
+    /// or if the numerator is greater than the denominator.
+    ///
+    /// ```
+    /// use phenotypes::{Fraction, PhenotypesError};
+    ///
+    /// let f: Fraction<u8> = Fraction::try_from_signed(3i32, 10).unwrap();
+    /// assert_eq!(f, Fraction::try_from((3, 10)).unwrap());
+    ///
+    /// let err = Fraction::<u8>::try_from_signed(-1i64, 10).unwrap_err();
+    /// assert_eq!(err, PhenotypesError::NegativeCount { value: "-1".to_string() });
+    ///
+    /// let err = Fraction::<u8>::try_from_signed(1, 1000).unwrap_err();
+    /// assert_eq!(err, PhenotypesError::CountOutOfRange { value: "1000".to_string() });
+    /// ```
+    pub fn try_from_signed<S>(n: S, m: S) -> Result<Self, PhenotypesError>
+    where
+        S: Into<i128>,
+        T: TryFrom<i128>,
+    {
+        let to_count = |value: i128| {
+            if value < 0 {
+                Err(PhenotypesError::NegativeCount {
+                    value: value.to_string(),
+                })
+            } else {
+                T::try_from(value).map_err(|_| PhenotypesError::CountOutOfRange {
+                    value: value.to_string(),
+                })
+            }
+        };
+        Fraction::try_from((to_count(n.into())?, to_count(m.into())?))
+    }
+}
+
+macro_rules! impl_widening_from {
+    ($($from:ty => $($to:ty),+);* $(;)?) => {
+        $($(
+            impl From<Fraction<$from>> for Fraction<$to> {
+                fn from(value: Fraction<$from>) -> Self {
+                    Fraction {
+                        n: <$to>::from(value.n),
+                        m: <$to>::from(value.m),
+                    }
+                }
+            }
+        )+)*
+    };
+}
+
+macro_rules! impl_narrowing_try_from {
+    ($($from:ty => $($to:ty),+);* $(;)?) => {
+        $($(
+            impl TryFrom<Fraction<$from>> for Fraction<$to> {
+                type Error = PhenotypesError;
+
+                fn try_from(value: Fraction<$from>) -> Result<Self, Self::Error> {
+                    let m = <$to>::try_from(value.m.clone()).map_err(|_| {
+                        PhenotypesError::CountOutOfRange {
+                            value: value.m.to_string(),
+                        }
+                    })?;
+                    // The numerator fits if the denominator does, since `n <= m`.
+                    let n = <$to>::try_from(value.n)
+                        .expect("The numerator should fit if the denominator does");
+                    Ok(Fraction { n, m })
+                }
+            }
+        )+)*
+    };
+}
+
+impl_widening_from! {
+    u8 => u16, u32, u64, u128, usize;
+    u16 => u32, u64, u128, usize;
+    u32 => u64, u128;
+    u64 => u128;
+}
+
+impl_narrowing_try_from! {
+    u16 => u8;
+    u32 => u8, u16, usize;
+    u64 => u8, u16, u32, usize;
+    u128 => u8, u16, u32, u64, usize;
+    usize => u8, u16, u32, u64, u128;
+}
+
+#[cfg(feature = "bigint")]
+impl_widening_from! {
+    u8 => num_bigint::BigUint;
+    u16 => num_bigint::BigUint;
+    u32 => num_bigint::BigUint;
+    u64 => num_bigint::BigUint;
+    u128 => num_bigint::BigUint;
+    usize => num_bigint::BigUint;
+}
+
+#[cfg(feature = "bigint")]
+impl_narrowing_try_from! {
+    num_bigint::BigUint => u8, u16, u32, u64, u128, usize;
+}
+
+/// Format the `Fraction` as `n/m`.
+///
+/// ```
+/// use phenotypes::Fraction;
+///
+/// let f = Fraction::try_from((3u32, 10)).unwrap();
+///
+/// assert_eq!(f.to_string(), "3/10");
+/// ```
+impl<T> Display for Fraction<T>
+where
+    T: Count,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.n, self.m)
+    }
+}
+
+/// The policy for parsing a percentage such as `30%` into a [`Fraction`].
+///
+/// A percentage carries no denominator,
+/// so we must choose one or refuse to parse the percentage altogether.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PercentagePolicy {
+    /// Use the denominator and round the numerator to the nearest integer,
+    /// e.g. `30%` is parsed into `30/100` with `Denominator(100)`
+    /// and `33.3%` into `1/3` with `Denominator(3)`.
+    ///
+    /// The denominator must not be zero, as `0/0` is not a percentage.
+    Denominator(u64),
+    /// Reject the percentages.
+    Reject,
+}
+
+/// Use `100` as the denominator.
+impl Default for PercentagePolicy {
+    fn default() -> Self {
+        PercentagePolicy::Denominator(100)
+    }
+}
+
+/// The part of the input that could not be parsed into a [`Fraction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FractionPart {
+    /// The input is empty or it is neither `n/m` nor a percentage.
+    Format,
+    /// The numerator is not a valid count.
+    Numerator,
+    /// The denominator is not a valid count.
+    Denominator,
+    /// The percentage is not a number in the `[0, 100]` range
+    /// or percentages are rejected by the [`PercentagePolicy`].
+    Percentage,
+}
+
+impl<T> Fraction<T>
+where
+    T: Count + FromStr + TryFrom<u64>,
+{
+    /// Parse the `Fraction` from `n/m` or from a percentage
+    /// using the provided [`PercentagePolicy`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use phenotypes::{Fraction, PercentagePolicy};
+    ///
+    /// let f: Fraction = Fraction::parse_with_policy("25%", PercentagePolicy::Denominator(8)).unwrap();
+    /// assert_eq!(f.to_string(), "2/8");
+    ///
+    /// assert!(Fraction::<u32>::parse_with_policy("25%", PercentagePolicy::Reject).is_err());
+    /// ```
+    ///
+    /// A zero denominator is rejected:
+    ///
+    /// ```
+    /// use phenotypes::{Fraction, FractionPart, PercentagePolicy, PhenotypesError};
+    ///
+    /// let err = Fraction::<u32>::parse_with_policy("100%", PercentagePolicy::Denominator(0))
+    ///     .unwrap_err();
+    /// assert_eq!(
+    ///     err,
+    ///     PhenotypesError::ParseFraction { input: "100%".into(), part: FractionPart::Denominator },
+    /// );
+    /// ```
+    pub fn parse_with_policy(s: &str, policy: PercentagePolicy) -> Result<Self, PhenotypesError> {
+        let value = s.trim();
+        if let Some((n, m)) = value.split_once('/') {
+            let n = n
+                .trim()
+                .parse()
+                .map_err(|_| parse_error(s, FractionPart::Numerator))?;
+            let m = m
+                .trim()
+                .parse()
+                .map_err(|_| parse_error(s, FractionPart::Denominator))?;
+            Fraction::try_from((n, m))
+        } else if let Some(percentage) = value.strip_suffix('%') {
+            let denominator = match policy {
+                PercentagePolicy::Denominator(0) => {
+                    return Err(parse_error(s, FractionPart::Denominator));
+                }
+                PercentagePolicy::Denominator(denominator) => denominator,
+                PercentagePolicy::Reject => {
+                    return Err(parse_error(s, FractionPart::Percentage));
+                }
+            };
+            let percentage: f64 = percentage
+                .trim()
+                .parse()
+                .map_err(|_| parse_error(s, FractionPart::Percentage))?;
+            if !(0. ..=100.).contains(&percentage) {
+                return Err(parse_error(s, FractionPart::Percentage));
+            }
+            let numerator = (percentage / 100. * denominator as f64).round() as u64;
+            let n = T::try_from(numerator).map_err(|_| parse_error(s, FractionPart::Numerator))?;
+            let m =
+                T::try_from(denominator).map_err(|_| parse_error(s, FractionPart::Denominator))?;
+            Fraction::try_from((n, m))
+        } else {
+            Err(parse_error(s, FractionPart::Format))
+        }
+    }
+}
+
+/// Parse the `Fraction` from `n/m` or from a percentage.
+///
+/// The whitespace around the numbers is ignored
+/// and the percentages are parsed using the default [`PercentagePolicy`].
+///
+/// ## Examples
+///
+/// ```
+/// use phenotypes::{Fraction, FractionPart, PhenotypesError};
+///
+/// let f: Fraction = "3 / 10".parse().unwrap();
+/// assert_eq!(f.n(), 3);
+/// assert_eq!(f.m(), 10);
+///
+/// let f: Fraction = "30%".parse().unwrap();
+/// assert_eq!(f.to_string(), "30/100");
+///
+/// let err = "3/x".parse::<Fraction>().unwrap_err();
+/// assert_eq!(
+///     err,
+///     PhenotypesError::ParseFraction { input: "3/x".into(), part: FractionPart::Denominator },
+/// );
+///
+/// let err = "5/3".parse::<Fraction>().unwrap_err();
+/// assert!(matches!(err, PhenotypesError::NumeratorExceedsDenominator { .. }));
+/// ```
+impl<T> FromStr for Fraction<T>
+where
+    T: Count + FromStr + TryFrom<u64>,
+{
+    type Err = PhenotypesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Fraction::parse_with_policy(s, PercentagePolicy::default())
+    }
+}
+
+fn parse_error(input: &str, part: FractionPart) -> PhenotypesError {
+    PhenotypesError::ParseFraction {
+        input: input.to_string(),
+        part,
+    }
+}
+
+/// The `Sex` of an individual or the sex-specificity of an annotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Sex {
+    Female,
+    Male,
+    /// The sex is neither female nor male.
+    Other,
+    /// The sex is not known or was not recorded.
+    Unknown,
+}
@@ -0,0 +1,413 @@
+//! A module for the modifiers of phenotypic features,
+//! such as *severe*, *bilateral* or *progressive*.
+//!
+//! The modifiers are the terms of the
+//! [Clinical modifier (HP:0012823)](https://hpo.jax.org/browse/term/HP:0012823) HPO sub-module.
+//! The most common modifiers are modeled by the typed [`Severity`], [`Laterality`],
+//! and [`Progression`] enums, and the other modifiers, such as
+//! [Episodic (HP:0025303)](https://hpo.jax.org/browse/term/HP:0025303), are kept as term IDs.
+//!
+//! ## Examples
+//!
+//! ```
+//! use ontolius::TermId;
+//! use phenotypes::Fraction;
+//! use phenotypes::modifiers::{Laterality, ModifiedFeatures, Modifiers, Severity};
+//! use phenotypes::simple::SimplePhenotypicFeature;
+//!
+//! let episodic: TermId = "HP:0025303".parse().unwrap();
+//! let feature = |curie: &str, modifiers| SimplePhenotypicFeature::new(
+//!     curie.parse().unwrap(),
+//!     Fraction::try_from((1u32, 1)).unwrap(),
+//! ).with_modifiers(modifiers);
+//!
+//! let patient = vec![
+//!     feature("HP:0010442", Modifiers::default().with_laterality(Laterality::Bilateral)),
+//!     feature("HP:0001250", Modifiers::from_term_ids([
+//!         "HP:0012828".parse().unwrap(), // Severe
+//!         episodic.clone(),
+//!     ])),
+//! ];
+//!
+//! assert_eq!(patient.features_with_severity(Severity::Severe).count(), 1);
+//! assert_eq!(patient.features_with_laterality(Laterality::Bilateral).count(), 1);
+//! assert_eq!(patient.features_with_modifier(&episodic).count(), 1);
+//! ```
+use std::fmt::{Display, Formatter};
+use std::sync::LazyLock;
+
+use ontolius::{Identified, TermId};
+
+use crate::{ObservableFeatures, PhenotypesError};
+
+/// Define an enum whose variants correspond to HPO terms,
+/// along with the term ID getter and the conversions.
+macro_rules! hpo_term_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident ($expected:literal) {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident => ($static_name:ident, $id:literal, $label:literal),
+            )+
+        }
+    ) => {
+        $(
+            static $static_name: LazyLock<TermId> = LazyLock::new(|| TermId::from(("HP", $id)));
+        )+
+
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )+
+        }
+
+        impl $name {
+            /// Get the ID of the HPO term that corresponds to the modifier.
+            pub fn term_id(&self) -> &'static TermId {
+                match self {
+                    $($name::$variant => &$static_name,)+
+                }
+            }
+        }
+
+        impl Identified for $name {
+            fn identifier(&self) -> &TermId {
+                self.term_id()
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                let label = match self {
+                    $($name::$variant => $label,)+
+                };
+                f.write_str(label)
+            }
+        }
+
+        /// Get the modifier of an HPO term ID.
+        ///
+        /// Fails if the term ID is not one of the modifier terms.
+        impl TryFrom<&TermId> for $name {
+            type Error = PhenotypesError;
+
+            fn try_from(value: &TermId) -> Result<Self, Self::Error> {
+                [$($name::$variant,)+]
+                    .into_iter()
+                    .find(|modifier| modifier.term_id() == value)
+                    .ok_or_else(|| PhenotypesError::UnexpectedTerm {
+                        term_id: value.clone(),
+                        expected: $expected.to_string(),
+                    })
+            }
+        }
+    };
+}
+
+hpo_term_enum! {
+    /// The severity of a feature, a term of the
+    /// [Severity (HP:0012824)](https://hpo.jax.org/browse/term/HP:0012824) HPO sub-module.
+    ///
+    /// ```
+    /// use ontolius::TermId;
+    /// use phenotypes::modifiers::Severity;
+    ///
+    /// let term_id: TermId = "HP:0012828".parse().unwrap();
+    /// let severity = Severity::try_from(&term_id).unwrap();
+    ///
+    /// assert_eq!(severity, Severity::Severe);
+    /// assert_eq!(severity.to_string(), "Severe");
+    /// assert!(Severity::Mild < Severity::Severe);
+    /// ```
+    pub enum Severity ("an HPO severity term") {
+        /// [Borderline (HP:0012827)](https://hpo.jax.org/browse/term/HP:0012827).
+        Borderline => (BORDERLINE, "0012827", "Borderline"),
+        /// [Mild (HP:0012825)](https://hpo.jax.org/browse/term/HP:0012825).
+        Mild => (MILD, "0012825", "Mild"),
+        /// [Moderate (HP:0012826)](https://hpo.jax.org/browse/term/HP:0012826).
+        Moderate => (MODERATE, "0012826", "Moderate"),
+        /// [Severe (HP:0012828)](https://hpo.jax.org/browse/term/HP:0012828).
+        Severe => (SEVERE, "0012828", "Severe"),
+        /// [Profound (HP:0012829)](https://hpo.jax.org/browse/term/HP:0012829).
+        Profound => (PROFOUND, "0012829", "Profound"),
+    }
+}
+
+hpo_term_enum! {
+    /// The laterality of a feature, a term of the
+    /// [Laterality (HP:0012831)](https://hpo.jax.org/browse/term/HP:0012831) HPO sub-module.
+    ///
+    /// ```
+    /// use phenotypes::modifiers::Laterality;
+    ///
+    /// assert_eq!(Laterality::Bilateral.term_id().to_string(), "HP:0012832");
+    /// ```
+    pub enum Laterality ("an HPO laterality term") {
+        /// [Bilateral (HP:0012832)](https://hpo.jax.org/browse/term/HP:0012832).
+        Bilateral => (BILATERAL, "0012832", "Bilateral"),
+        /// [Unilateral (HP:0012833)](https://hpo.jax.org/browse/term/HP:0012833).
+        Unilateral => (UNILATERAL, "0012833", "Unilateral"),
+        /// [Right (HP:0012834)](https://hpo.jax.org/browse/term/HP:0012834).
+        Right => (RIGHT, "0012834", "Right"),
+        /// [Left (HP:0012835)](https://hpo.jax.org/browse/term/HP:0012835).
+        Left => (LEFT, "0012835", "Left"),
+    }
+}
+
+hpo_term_enum! {
+    /// The progression of a feature, a term of the
+    /// [Clinical course (HP:0031797)](https://hpo.jax.org/browse/term/HP:0031797) HPO sub-module.
+    ///
+    /// ```
+    /// use phenotypes::modifiers::Progression;
+    ///
+    /// assert_eq!(Progression::Progressive.term_id().to_string(), "HP:0003676");
+    /// ```
+    pub enum Progression ("an HPO progression term") {
+        /// [Progressive (HP:0003676)](https://hpo.jax.org/browse/term/HP:0003676).
+        Progressive => (PROGRESSIVE, "0003676", "Progressive"),
+        /// [Slow progression (HP:0003677)](https://hpo.jax.org/browse/term/HP:0003677).
+        SlowProgression => (SLOW_PROGRESSION, "0003677", "Slow progression"),
+        /// [Rapidly progressive (HP:0003678)](https://hpo.jax.org/browse/term/HP:0003678).
+        RapidlyProgressive => (RAPIDLY_PROGRESSIVE, "0003678", "Rapidly progressive"),
+        /// [Nonprogressive (HP:0003680)](https://hpo.jax.org/browse/term/HP:0003680).
+        Nonprogressive => (NONPROGRESSIVE, "0003680", "Nonprogressive"),
+    }
+}
+
+/// The modifiers of a phenotypic feature.
+///
+/// ```
+/// use ontolius::TermId;
+/// use phenotypes::modifiers::{Modifiers, Progression, Severity};
+///
+/// let episodic: TermId = "HP:0025303".parse().unwrap();
+/// let modifiers = Modifiers::from_term_ids([
+///     "HP:0012826".parse().unwrap(),
+///     "HP:0003676".parse().unwrap(),
+///     episodic.clone(),
+/// ]);
+///
+/// assert_eq!(modifiers.severity(), Some(Severity::Moderate));
+/// assert_eq!(modifiers.progression(), Some(Progression::Progressive));
+/// assert_eq!(modifiers.laterality(), None);
+/// assert_eq!(modifiers.other(), &[episodic.clone()]);
+///
+/// assert!(modifiers.contains(&episodic));
+/// assert!(modifiers.contains(Severity::Moderate.term_id()));
+/// assert_eq!(modifiers.term_ids().count(), 3);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Modifiers {
+    severity: Option<Severity>,
+    laterality: Option<Laterality>,
+    progression: Option<Progression>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie::vec"))]
+    other: Vec<TermId>,
+}
+
+impl Modifiers {
+    /// Sort the modifier term IDs into the typed modifiers and the other modifiers.
+    ///
+    /// If more term IDs correspond to the same typed modifier, such as *Mild* and *Severe*,
+    /// the first one becomes the typed modifier and the conflicting ones are kept
+    /// among the [`Modifiers::other`] modifiers. The repeated term IDs are ignored.
+    ///
+    /// ```
+    /// use ontolius::TermId;
+    /// use phenotypes::modifiers::{Modifiers, Severity};
+    ///
+    /// let mild: TermId = "HP:0012825".parse().unwrap();
+    /// let severe: TermId = "HP:0012828".parse().unwrap();
+    /// let modifiers = Modifiers::from_term_ids([mild.clone(), severe.clone(), mild]);
+    ///
+    /// assert_eq!(modifiers.severity(), Some(Severity::Mild));
+    /// assert_eq!(modifiers.other(), &[severe]);
+    /// assert_eq!(modifiers.term_ids().count(), 2);
+    /// ```
+    pub fn from_term_ids<I>(term_ids: I) -> Self
+    where
+        I: IntoIterator<Item = TermId>,
+    {
+        term_ids
+            .into_iter()
+            .fold(Modifiers::default(), Modifiers::with_other)
+    }
+
+    /// Set the severity, replacing the current severity.
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.other.retain(|term_id| term_id != severity.term_id());
+        self.severity = Some(severity);
+        self
+    }
+
+    /// Set the laterality, replacing the current laterality.
+    pub fn with_laterality(mut self, laterality: Laterality) -> Self {
+        self.other.retain(|term_id| term_id != laterality.term_id());
+        self.laterality = Some(laterality);
+        self
+    }
+
+    /// Set the progression, replacing the current progression.
+    pub fn with_progression(mut self, progression: Progression) -> Self {
+        self.other
+            .retain(|term_id| term_id != progression.term_id());
+        self.progression = Some(progression);
+        self
+    }
+
+    /// Add a modifier term ID.
+    ///
+    /// The term ID is sorted into the typed modifiers
+    /// in the same way as by [`Modifiers::from_term_ids`].
+    ///
+    /// ```
+    /// use ontolius::TermId;
+    /// use phenotypes::modifiers::{Modifiers, Severity};
+    ///
+    /// let severe: TermId = "HP:0012828".parse().unwrap();
+    /// let modifiers = Modifiers::default().with_other(severe.clone());
+    /// assert_eq!(modifiers.severity(), Some(Severity::Severe));
+    /// assert!(modifiers.other().is_empty());
+    ///
+    /// // The conflicting severity is kept among the other modifiers.
+    /// let modifiers = Modifiers::default()
+    ///     .with_severity(Severity::Mild)
+    ///     .with_other(severe.clone())
+    ///     .with_other(severe.clone());
+    /// assert_eq!(modifiers.severity(), Some(Severity::Mild));
+    /// assert_eq!(modifiers.other(), &[severe]);
+    /// ```
+    pub fn with_other(mut self, term_id: TermId) -> Self {
+        if self.contains(&term_id) {
+            return self;
+        }
+        // The typed modifiers are disjoint, hence a conflicting term ID
+        // falls through to the other modifiers.
+        if let Ok(severity) = Severity::try_from(&term_id)
+            && self.severity.is_none()
+        {
+            self.severity = Some(severity);
+        } else if let Ok(laterality) = Laterality::try_from(&term_id)
+            && self.laterality.is_none()
+        {
+            self.laterality = Some(laterality);
+        } else if let Ok(progression) = Progression::try_from(&term_id)
+            && self.progression.is_none()
+        {
+            self.progression = Some(progression);
+        } else {
+            self.other.push(term_id);
+        }
+        self
+    }
+
+    /// Get the severity.
+    pub fn severity(&self) -> Option<Severity> {
+        self.severity
+    }
+
+    /// Get the laterality.
+    pub fn laterality(&self) -> Option<Laterality> {
+        self.laterality
+    }
+
+    /// Get the progression.
+    pub fn progression(&self) -> Option<Progression> {
+        self.progression
+    }
+
+    /// Get the modifiers that have no typed representation
+    /// or that conflict with the typed modifiers.
+    pub fn other(&self) -> &[TermId] {
+        &self.other
+    }
+
+    /// Test if there are no modifiers.
+    pub fn is_empty(&self) -> bool {
+        self.severity.is_none()
+            && self.laterality.is_none()
+            && self.progression.is_none()
+            && self.other.is_empty()
+    }
+
+    /// Get an iterator over the term IDs of all modifiers.
+    pub fn term_ids(&self) -> impl Iterator<Item = &TermId> {
+        self.severity
+            .map(|severity| severity.term_id())
+            .into_iter()
+            .chain(self.laterality.map(|laterality| laterality.term_id()))
+            .chain(self.progression.map(|progression| progression.term_id()))
+            .chain(self.other.iter())
+    }
+
+    /// Test if the modifier `term_id` is among the modifiers.
+    pub fn contains(&self, term_id: &TermId) -> bool {
+        self.term_ids().any(|modifier| modifier == term_id)
+    }
+}
+
+/// An entity with [`Modifiers`], such as a phenotypic feature.
+pub trait ModifierAware {
+    /// Get the modifiers.
+    fn modifiers(&self) -> &Modifiers;
+}
+
+/// Extension of [`ObservableFeatures`] with [`ModifierAware`] features
+/// for selecting the present features by their modifiers.
+///
+/// The trait is implemented for all such containers.
+pub trait ModifiedFeatures: ObservableFeatures
+where
+    Self::Feature: ModifierAware,
+{
+    /// Get an iterator over the present features with the `severity`.
+    fn features_with_severity(&self, severity: Severity) -> impl Iterator<Item = &Self::Feature> {
+        self.present_features()
+            .filter(move |feature| feature.modifiers().severity() == Some(severity))
+    }
+
+    /// Get an iterator over the present features with the `laterality`.
+    fn features_with_laterality(
+        &self,
+        laterality: Laterality,
+    ) -> impl Iterator<Item = &Self::Feature> {
+        self.present_features()
+            .filter(move |feature| feature.modifiers().laterality() == Some(laterality))
+    }
+
+    /// Get an iterator over the present features with the `progression`.
+    fn features_with_progression(
+        &self,
+        progression: Progression,
+    ) -> impl Iterator<Item = &Self::Feature> {
+        self.present_features()
+            .filter(move |feature| feature.modifiers().progression() == Some(progression))
+    }
+
+    /// Get an iterator over the present features with the modifier `term_id`,
+    /// either typed or not.
+    fn features_with_modifier<'a>(
+        &'a self,
+        term_id: &'a TermId,
+    ) -> impl Iterator<Item = &'a Self::Feature>
+    where
+        Self::Feature: 'a,
+    {
+        self.present_features()
+            .filter(move |feature| feature.modifiers().contains(term_id))
+    }
+}
+
+impl<T> ModifiedFeatures for T
+where
+    T: ObservableFeatures,
+    T::Feature: ModifierAware,
+{
+}
@@ -1,14 +1,62 @@
-/// An `Observable` entity is either in a *present* or an *excluded* state
+/// The state of an [`Observable`] entity in the investigated item.
+///
+/// Besides being *present* or *excluded*, a feature can be in an *unknown* state,
+/// e.g. if the item was never examined for the presence of the feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ObservationState {
+    /// The feature was observed in the investigated item.
+    Present,
+    /// The presence of the feature was specifically excluded.
+    Excluded,
+    /// The feature was not assessed or measured,
+    /// and we know nothing about its presence.
+    Unknown,
+}
+
+/// An `Observable` entity is either in a *present*, an *excluded*, or an *unknown* state
 /// in the investigated item.
 ///
 /// For instance, a phenotypic feature such as [Polydactyly](https://hpo.jax.org/browse/term/HP:0010442)
-/// can either be present or excluded in the study subject.
+/// can either be present or excluded in the study subject,
+/// or the subject may not have been examined for the feature at all.
+///
+/// ## Examples
+///
+/// ```
+/// use phenotypes::{Observable, ObservationState};
+///
+/// struct Polydactyly(ObservationState);
+///
+/// impl Observable for Polydactyly {
+///     fn observation_state(&self) -> ObservationState {
+///         self.0
+///     }
+/// }
+///
+/// let unknown = Polydactyly(ObservationState::Unknown);
+///
+/// assert!(!unknown.is_present());
+/// assert!(!unknown.is_excluded());
+/// assert!(unknown.is_unknown());
+/// ```
 pub trait Observable {
+    /// Get the state of the feature in the investigated item.
+    fn observation_state(&self) -> ObservationState;
+
     /// Test if the feature was observed in one or more items.
-    fn is_present(&self) -> bool;
+    fn is_present(&self) -> bool {
+        self.observation_state() == ObservationState::Present
+    }
+
     /// Test if the feature was not observed in any of the items.
     fn is_excluded(&self) -> bool {
-        !self.is_present()
+        self.observation_state() == ObservationState::Excluded
+    }
+
+    /// Test if the feature was neither observed nor excluded.
+    fn is_unknown(&self) -> bool {
+        self.observation_state() == ObservationState::Unknown
     }
 }
 
@@ -30,8 +78,45 @@ pub trait ObservableFeatures {
     fn excluded_feature_count(&self) -> usize {
         self.excluded_features().count()
     }
+
+    /// Get an iterator over features that were neither observed nor excluded in the investigated item.
+    ///
+    /// The default implementation yields no features,
+    /// which suits containers that only track present and excluded features.
+    fn unknown_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        std::iter::empty()
+    }
+    /// Get the number of features that were neither observed nor excluded.
+    fn unknown_feature_count(&self) -> usize {
+        self.unknown_features().count()
+    }
 }
 
+/// The unknown features fall in neither the present nor the excluded bucket.
+///
+/// ```
+/// use phenotypes::{Observable, ObservableFeatures, ObservationState};
+///
+/// struct Feature(ObservationState);
+///
+/// impl Observable for Feature {
+///     fn observation_state(&self) -> ObservationState {
+///         self.0
+///     }
+/// }
+///
+/// let features = [
+///     Feature(ObservationState::Present),
+///     Feature(ObservationState::Excluded),
+///     Feature(ObservationState::Unknown),
+///     Feature(ObservationState::Unknown),
+/// ];
+/// let features = &features[..];
+///
+/// assert_eq!(features.present_feature_count(), 1);
+/// assert_eq!(features.excluded_feature_count(), 1);
+/// assert_eq!(features.unknown_feature_count(), 2);
+/// ```
 impl<T> ObservableFeatures for &[T]
 where
     T: Observable,
@@ -45,6 +130,10 @@ where
     fn excluded_features(&self) -> impl Iterator<Item = &Self::Feature> {
         self.iter().filter(|&t| t.is_excluded())
     }
+
+    fn unknown_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        self.iter().filter(|&t| t.is_unknown())
+    }
 }
 
 impl<T, const N: usize> ObservableFeatures for [T; N]
@@ -60,6 +149,10 @@ where
     fn excluded_features(&self) -> impl Iterator<Item = &Self::Feature> {
         self.iter().filter(|&t| t.is_excluded())
     }
+
+    fn unknown_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        self.iter().filter(|&t| t.is_unknown())
+    }
 }
 
 impl<T> ObservableFeatures for Vec<T>
@@ -75,4 +168,8 @@ where
     fn excluded_features(&self) -> impl Iterator<Item = &Self::Feature> {
         self.iter().filter(|&t| t.is_excluded())
     }
+
+    fn unknown_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        self.iter().filter(|&t| t.is_unknown())
+    }
 }
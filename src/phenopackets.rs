@@ -0,0 +1,337 @@
+//! A module for reading [GA4GH Phenopacket Schema v2](https://phenopacket-schema.readthedocs.io) JSON.
+//!
+//! The module is available with the `phenopackets` feature.
+//!
+//! The types model a subset of the schema that is relevant for the crate.
+//! The [`PhenotypicFeature`] implements [`Observable`] and [`Identified`],
+//! and the [`ObservedSubject`] of the [`Phenopacket`] implements [`ObservableFeatures`],
+//! so that the phenopacket subject can be used with the APIs of the crate.
+//! The elements of the schema that are not modeled here are ignored when reading the JSON.
+//!
+//! ## Examples
+//!
+//! ```
+//! use phenotypes::{Observable, ObservableFeatures};
+//! use phenotypes::phenopackets::Phenopacket;
+//!
+//! let json = r#"{
+//!   "id": "example",
+//!   "subject": { "id": "proband", "sex": "FEMALE" },
+//!   "phenotypicFeatures": [
+//!     { "type": { "id": "HP:0010442", "label": "Polydactyly" } },
+//!     { "type": { "id": "HP:0001250", "label": "Seizure" }, "excluded": true }
+//!   ]
+//! }"#;
+//!
+//! let phenopacket = Phenopacket::from_json_str(json).expect("The phenopacket is valid");
+//! assert_eq!(phenopacket.id(), "example");
+//!
+//! let subject = phenopacket.observed_subject();
+//! assert_eq!(subject.individual().unwrap().id(), "proband");
+//! assert_eq!(subject.present_feature_count(), 1);
+//! assert_eq!(subject.excluded_feature_count(), 1);
+//!
+//! let seizure = subject.excluded_features().next().unwrap();
+//! assert_eq!(seizure.r#type().label(), "Seizure");
+//! assert!(seizure.is_excluded());
+//! ```
+use std::io::Read;
+
+use ontolius::{Identified, TermId};
+use serde::{Deserialize, Serialize};
+
+use crate::{Observable, ObservableFeatures, ObservationState, PhenotypesError, Sex};
+
+/// A concept from an ontology, such as an HPO term.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OntologyClass {
+    #[serde(with = "crate::serde_curie")]
+    id: TermId,
+    #[serde(default)]
+    label: String,
+}
+
+impl OntologyClass {
+    pub fn new(id: TermId, label: impl ToString) -> Self {
+        Self {
+            id,
+            label: label.to_string(),
+        }
+    }
+
+    /// Get the label of the concept.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+}
+
+impl Identified for OntologyClass {
+    fn identifier(&self) -> &TermId {
+        &self.id
+    }
+}
+
+/// The representation of the crate's [`Sex`] in the Phenopacket Schema,
+/// e.g. `UNKNOWN_SEX` or `FEMALE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum SchemaSex {
+    #[default]
+    UnknownSex,
+    Female,
+    Male,
+    OtherSex,
+}
+
+impl From<SchemaSex> for Sex {
+    fn from(value: SchemaSex) -> Self {
+        match value {
+            SchemaSex::UnknownSex => Sex::Unknown,
+            SchemaSex::Female => Sex::Female,
+            SchemaSex::Male => Sex::Male,
+            SchemaSex::OtherSex => Sex::Other,
+        }
+    }
+}
+
+impl From<Sex> for SchemaSex {
+    fn from(value: Sex) -> Self {
+        match value {
+            Sex::Unknown => SchemaSex::UnknownSex,
+            Sex::Female => SchemaSex::Female,
+            Sex::Male => SchemaSex::Male,
+            Sex::Other => SchemaSex::OtherSex,
+        }
+    }
+}
+
+/// The subject of a [`Phenopacket`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Individual {
+    id: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    alternate_ids: Vec<String>,
+    #[serde(default)]
+    sex: SchemaSex,
+}
+
+impl Individual {
+    /// Get the identifier of the individual.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the alternate identifiers of the individual.
+    pub fn alternate_ids(&self) -> &[String] {
+        &self.alternate_ids
+    }
+
+    /// Get the phenotypic sex of the individual.
+    ///
+    /// ```
+    /// use phenotypes::Sex;
+    /// use phenotypes::phenopackets::Phenopacket;
+    ///
+    /// let json = r#"{ "id": "example", "subject": { "id": "proband", "sex": "OTHER_SEX" } }"#;
+    /// let phenopacket = Phenopacket::from_json_str(json).unwrap();
+    /// assert_eq!(phenopacket.subject().unwrap().sex(), Sex::Other);
+    ///
+    /// let json = r#"{ "id": "example", "subject": { "id": "proband" } }"#;
+    /// let phenopacket = Phenopacket::from_json_str(json).unwrap();
+    /// assert_eq!(phenopacket.subject().unwrap().sex(), Sex::Unknown);
+    /// ```
+    pub fn sex(&self) -> Sex {
+        Sex::from(self.sex)
+    }
+}
+
+/// A phenotypic feature observed or excluded in the subject.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhenotypicFeature {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    r#type: OntologyClass,
+    #[serde(default)]
+    excluded: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    severity: Option<OntologyClass>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    modifiers: Vec<OntologyClass>,
+}
+
+impl PhenotypicFeature {
+    /// Get the free-text description of the feature.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Get the ontology class of the feature, e.g. an HPO term.
+    pub fn r#type(&self) -> &OntologyClass {
+        &self.r#type
+    }
+
+    /// Get the severity of the feature.
+    pub fn severity(&self) -> Option<&OntologyClass> {
+        self.severity.as_ref()
+    }
+
+    /// Get the modifiers of the feature.
+    pub fn modifiers(&self) -> &[OntologyClass] {
+        &self.modifiers
+    }
+}
+
+impl Identified for PhenotypicFeature {
+    fn identifier(&self) -> &TermId {
+        self.r#type.identifier()
+    }
+}
+
+/// The feature is excluded if the `excluded` flag is set and present otherwise.
+impl Observable for PhenotypicFeature {
+    fn observation_state(&self) -> ObservationState {
+        if self.excluded {
+            ObservationState::Excluded
+        } else {
+            ObservationState::Present
+        }
+    }
+}
+
+/// A disease diagnosed or excluded in the subject.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Disease {
+    term: OntologyClass,
+    #[serde(default)]
+    excluded: bool,
+}
+
+impl Disease {
+    /// Get the ontology class of the disease, e.g. an OMIM term.
+    pub fn term(&self) -> &OntologyClass {
+        &self.term
+    }
+
+    /// Test if the disease was excluded in the subject.
+    pub fn is_excluded(&self) -> bool {
+        self.excluded
+    }
+}
+
+impl Identified for Disease {
+    fn identifier(&self) -> &TermId {
+        self.term.identifier()
+    }
+}
+
+/// A phenopacket with the clinical information of a single subject.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Phenopacket {
+    id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    subject: Option<Individual>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    phenotypic_features: Vec<PhenotypicFeature>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    diseases: Vec<Disease>,
+}
+
+impl Phenopacket {
+    /// Read the phenopacket from a JSON string.
+    pub fn from_json_str(json: &str) -> Result<Self, PhenotypesError> {
+        serde_json::from_str(json).map_err(|e| PhenotypesError::InvalidJson {
+            reason: e.to_string(),
+        })
+    }
+
+    /// Read the phenopacket from a reader with JSON data.
+    pub fn from_json_reader<R>(read: R) -> Result<Self, PhenotypesError>
+    where
+        R: Read,
+    {
+        serde_json::from_reader(read).map_err(|e| PhenotypesError::InvalidJson {
+            reason: e.to_string(),
+        })
+    }
+
+    /// Get the identifier of the phenopacket.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Get the subject of the phenopacket.
+    pub fn subject(&self) -> Option<&Individual> {
+        self.subject.as_ref()
+    }
+
+    /// Get all phenotypic features of the subject, present or excluded.
+    pub fn phenotypic_features(&self) -> &[PhenotypicFeature] {
+        &self.phenotypic_features
+    }
+
+    /// Get the subject with the phenotypic features of the phenopacket.
+    ///
+    /// The features are available even if the phenopacket has no subject.
+    ///
+    /// ```
+    /// use phenotypes::ObservableFeatures;
+    /// use phenotypes::phenopackets::Phenopacket;
+    ///
+    /// let json = r#"{
+    ///   "id": "example",
+    ///   "phenotypicFeatures": [{ "type": { "id": "HP:0010442", "label": "Polydactyly" } }]
+    /// }"#;
+    /// let phenopacket = Phenopacket::from_json_str(json).unwrap();
+    ///
+    /// let subject = phenopacket.observed_subject();
+    /// assert!(subject.individual().is_none());
+    /// assert_eq!(subject.present_feature_count(), 1);
+    /// ```
+    pub fn observed_subject(&self) -> ObservedSubject<'_> {
+        ObservedSubject {
+            individual: self.subject.as_ref(),
+            phenotypic_features: &self.phenotypic_features,
+        }
+    }
+
+    /// Get the diseases diagnosed or excluded in the subject.
+    pub fn diseases(&self) -> &[Disease] {
+        &self.diseases
+    }
+}
+
+/// The subject of a [`Phenopacket`] with the phenotypic features of the phenopacket.
+///
+/// The Phenopacket Schema lists the features on the phenopacket rather than
+/// on the [`Individual`], hence the view pairs the two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObservedSubject<'a> {
+    individual: Option<&'a Individual>,
+    phenotypic_features: &'a [PhenotypicFeature],
+}
+
+impl<'a> ObservedSubject<'a> {
+    /// Get the individual or `None` if the phenopacket has no subject.
+    pub fn individual(&self) -> Option<&'a Individual> {
+        self.individual
+    }
+
+    /// Get all phenotypic features of the subject, present or excluded.
+    pub fn phenotypic_features(&self) -> &'a [PhenotypicFeature] {
+        self.phenotypic_features
+    }
+}
+
+impl ObservableFeatures for ObservedSubject<'_> {
+    type Feature = PhenotypicFeature;
+
+    fn present_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        self.phenotypic_features.iter().filter(|f| f.is_present())
+    }
+
+    fn excluded_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        self.phenotypic_features.iter().filter(|f| f.is_excluded())
+    }
+}
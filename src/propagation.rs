@@ -0,0 +1,104 @@
+//! A module for applying the ontology "true path rule" to [`ObservableFeatures`].
+//!
+//! A subject annotated with a present feature is implicitly annotated
+//! with all ancestors of the feature. For instance, a subject with
+//! [Postaxial polydactyly](https://hpo.jax.org/browse/term/HP:0100259)
+//! has [Polydactyly](https://hpo.jax.org/browse/term/HP:0010442) too.
+//! Conversely, an excluded feature implies all its descendants are excluded as well.
+//!
+//! ## Examples
+//!
+//! ```
+//! # let hpo = include!("../tests/common/toy_hpo.rs");
+//! use ontolius::{Identified, TermId};
+//! use phenotypes::{Observable, ObservationState};
+//! use phenotypes::propagation::PropagatedFeatures;
+//!
+//! struct Observation(TermId, ObservationState);
+//!
+//! impl Identified for Observation {
+//!     fn identifier(&self) -> &TermId {
+//!         &self.0
+//!     }
+//! }
+//!
+//! impl Observable for Observation {
+//!     fn observation_state(&self) -> ObservationState {
+//!         self.1
+//!     }
+//! }
+//!
+//! let subject = vec![
+//!     Observation("HP:0100259".parse().unwrap(), ObservationState::Present),
+//!     Observation("HP:0010442".parse().unwrap(), ObservationState::Present),
+//!     Observation("HP:0000707".parse().unwrap(), ObservationState::Excluded),
+//! ];
+//!
+//! // `hpo` is a small hand-built ontology with the HPO terms used in the example.
+//! let present: Vec<_> = subject.implied_present_term_ids(&hpo)
+//!     .map(TermId::to_string)
+//!     .collect();
+//! assert_eq!(
+//!     present,
+//!     ["HP:0100259", "HP:0010442", "HP:0040064", "HP:0000118", "HP:0000001"],
+//! );
+//!
+//! let excluded: Vec<_> = subject.implied_excluded_term_ids(&hpo)
+//!     .map(TermId::to_string)
+//!     .collect();
+//! assert_eq!(excluded, ["HP:0000707", "HP:0001250"]);
+//! ```
+use std::collections::HashSet;
+
+use ontolius::ontology::HierarchyWalks;
+use ontolius::{Identified, TermId};
+
+use crate::ObservableFeatures;
+
+/// Extension of [`ObservableFeatures`] with [`Identified`] features
+/// for propagating the features along the ontology hierarchy.
+///
+/// The trait is implemented for all such containers.
+pub trait PropagatedFeatures: ObservableFeatures
+where
+    Self::Feature: Identified,
+{
+    /// Get an iterator over the IDs of the present features and all their ancestors.
+    ///
+    /// Each term ID is reported once, in the order of the first appearance.
+    fn implied_present_term_ids<'a, O>(
+        &'a self,
+        hierarchy: &'a O,
+    ) -> impl Iterator<Item = &'a TermId>
+    where
+        O: HierarchyWalks,
+    {
+        let mut seen = HashSet::new();
+        self.present_features()
+            .flat_map(|feature| hierarchy.iter_term_and_ancestor_ids(feature.identifier()))
+            .filter(move |&term_id| seen.insert(term_id))
+    }
+
+    /// Get an iterator over the IDs of the excluded features and all their descendants.
+    ///
+    /// Each term ID is reported once, in the order of the first appearance.
+    fn implied_excluded_term_ids<'a, O>(
+        &'a self,
+        hierarchy: &'a O,
+    ) -> impl Iterator<Item = &'a TermId>
+    where
+        O: HierarchyWalks,
+    {
+        let mut seen = HashSet::new();
+        self.excluded_features()
+            .flat_map(|feature| hierarchy.iter_term_and_descendant_ids(feature.identifier()))
+            .filter(move |&term_id| seen.insert(term_id))
+    }
+}
+
+impl<T> PropagatedFeatures for T
+where
+    T: ObservableFeatures,
+    T::Feature: Identified,
+{
+}
@@ -0,0 +1,212 @@
+//! A module for tracking the evidence and the provenance of observations.
+//!
+//! A [`Provenance`] records where an observation came from: the supporting publications,
+//! the [`EvidenceCode`], the curator, and the date of the curation.
+//! Any [`Observable`] can be wrapped into [`WithProvenance`] to attach the provenance.
+//!
+//! ## Examples
+//!
+//! ```
+//! use ontolius::Identified;
+//! use phenotypes::{Fraction, Observable};
+//! use phenotypes::hpoa::EvidenceCode;
+//! use phenotypes::provenance::{Provenance, ProvenanceAware, WithProvenance};
+//! use phenotypes::simple::SimplePhenotypicFeature;
+//!
+//! let feature = SimplePhenotypicFeature::new(
+//!     "HP:0010442".parse().unwrap(),
+//!     Fraction::try_from((1u32, 1)).unwrap(),
+//! );
+//! let provenance = Provenance::default()
+//!     .with_reference("PMID:31675180")
+//!     .with_evidence(EvidenceCode::PublishedClinicalStudy)
+//!     .with_curator("ORCID:0000-0002-0736-9199")
+//!     .with_date("2024-05-31");
+//!
+//! let observation = WithProvenance::new(feature, provenance);
+//!
+//! assert!(observation.is_present());
+//! assert_eq!(observation.identifier().to_string(), "HP:0010442");
+//! assert_eq!(observation.provenance().references(), ["PMID:31675180"]);
+//! assert_eq!(observation.provenance().evidence(), Some(EvidenceCode::PublishedClinicalStudy));
+//! ```
+use ontolius::{Identified, TermId};
+
+use crate::hpoa::{EvidenceCode, HpoaRecord};
+use crate::modifiers::{ModifierAware, Modifiers};
+use crate::temporal::{TemporalObservable, TimeElement};
+use crate::{FrequencyAware, Observable, ObservationState};
+
+/// The evidence and the origin of an observation.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Provenance {
+    references: Vec<String>,
+    evidence: Option<EvidenceCode>,
+    curator: Option<String>,
+    date: Option<String>,
+}
+
+impl Provenance {
+    /// Add a supporting reference, such as a PMID (e.g. `PMID:31675180`).
+    pub fn with_reference(mut self, reference: impl Into<String>) -> Self {
+        self.references.push(reference.into());
+        self
+    }
+
+    /// Set the evidence code.
+    pub fn with_evidence(mut self, evidence: EvidenceCode) -> Self {
+        self.evidence = Some(evidence);
+        self
+    }
+
+    /// Set the curator, e.g. `HPO:skoehler` or an ORCID.
+    pub fn with_curator(mut self, curator: impl Into<String>) -> Self {
+        self.curator = Some(curator.into());
+        self
+    }
+
+    /// Set the date of the curation in the `YYYY-MM-DD` format.
+    pub fn with_date(mut self, date: impl Into<String>) -> Self {
+        self.date = Some(date.into());
+        self
+    }
+
+    /// Get the supporting references.
+    pub fn references(&self) -> &[String] {
+        &self.references
+    }
+
+    /// Get the evidence code.
+    pub fn evidence(&self) -> Option<EvidenceCode> {
+        self.evidence
+    }
+
+    /// Get the curator.
+    pub fn curator(&self) -> Option<&str> {
+        self.curator.as_deref()
+    }
+
+    /// Get the date of the curation.
+    pub fn date(&self) -> Option<&str> {
+        self.date.as_deref()
+    }
+}
+
+/// Get the provenance of an HPO annotation.
+///
+/// The curator and the date come from the first biocuration entry.
+///
+/// ```
+/// use phenotypes::hpoa::{EvidenceCode, HpoaRecord};
+/// use phenotypes::provenance::Provenance;
+///
+/// let line = "OMIM:619340\tDevelopmental and epileptic encephalopathy 96\t\tHP:0001250\tPMID:31675180\tPCS\t\t1/2\t\t\tP\tHPO:probinson[2021-06-21]";
+/// let record: HpoaRecord = line.parse().unwrap();
+///
+/// let provenance = Provenance::from(&record);
+/// assert_eq!(provenance.references(), ["PMID:31675180"]);
+/// assert_eq!(provenance.evidence(), Some(EvidenceCode::PublishedClinicalStudy));
+/// assert_eq!(provenance.curator(), Some("HPO:probinson"));
+/// assert_eq!(provenance.date(), Some("2021-06-21"));
+/// ```
+impl From<&HpoaRecord> for Provenance {
+    fn from(value: &HpoaRecord) -> Self {
+        let biocuration = value.biocuration().first();
+        Provenance {
+            references: value.references().to_vec(),
+            evidence: Some(value.evidence()),
+            curator: biocuration.map(|b| b.curator().to_string()),
+            date: biocuration.and_then(|b| b.date()).map(str::to_string),
+        }
+    }
+}
+
+/// An entity with a known [`Provenance`].
+pub trait ProvenanceAware {
+    /// Get the provenance.
+    fn provenance(&self) -> &Provenance;
+}
+
+/// An item, such as an [`Observable`] feature, with an attached [`Provenance`].
+///
+/// The wrapper exposes the [`Identified`], [`Observable`], [`FrequencyAware`],
+/// [`TemporalObservable`], and [`ModifierAware`] behavior of the wrapped item.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WithProvenance<T> {
+    item: T,
+    provenance: Provenance,
+}
+
+impl<T> WithProvenance<T> {
+    /// Attach the `provenance` to the `item`.
+    pub fn new(item: T, provenance: Provenance) -> Self {
+        Self { item, provenance }
+    }
+
+    /// Get the wrapped item.
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+
+    /// Unwrap the item and its provenance.
+    pub fn into_parts(self) -> (T, Provenance) {
+        (self.item, self.provenance)
+    }
+}
+
+impl<T> ProvenanceAware for WithProvenance<T> {
+    fn provenance(&self) -> &Provenance {
+        &self.provenance
+    }
+}
+
+impl<T> Identified for WithProvenance<T>
+where
+    T: Identified,
+{
+    fn identifier(&self) -> &TermId {
+        self.item.identifier()
+    }
+}
+
+impl<T> Observable for WithProvenance<T>
+where
+    T: Observable,
+{
+    fn observation_state(&self) -> ObservationState {
+        self.item.observation_state()
+    }
+}
+
+impl<T> FrequencyAware for WithProvenance<T>
+where
+    T: FrequencyAware,
+{
+    fn probability(&self) -> Option<f64> {
+        self.item.probability()
+    }
+}
+
+impl<T> TemporalObservable for WithProvenance<T>
+where
+    T: TemporalObservable,
+{
+    fn onset(&self) -> Option<&TimeElement> {
+        self.item.onset()
+    }
+
+    fn resolution(&self) -> Option<&TimeElement> {
+        self.item.resolution()
+    }
+}
+
+impl<T> ModifierAware for WithProvenance<T>
+where
+    T: ModifierAware,
+{
+    fn modifiers(&self) -> &Modifiers {
+        self.item.modifiers()
+    }
+}
@@ -0,0 +1,68 @@
+//! (De)serialization of [`TermId`] as a CURIE string, e.g. `"HP:0001250"`.
+//!
+//! Use with `#[serde(with = "crate::serde_curie")]`.
+use ontolius::TermId;
+use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+pub(crate) fn serialize<S>(term_id: &TermId, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(term_id)
+}
+
+pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<TermId, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let curie = String::deserialize(deserializer)?;
+    curie.parse().map_err(D::Error::custom)
+}
+
+/// (De)serialization of an optional [`TermId`].
+pub(crate) mod option {
+    use ontolius::TermId;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub(crate) fn serialize<S>(term_id: &Option<TermId>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match term_id {
+            Some(term_id) => serializer.collect_str(term_id),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<Option<TermId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|curie| curie.parse().map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+/// (De)serialization of a [`TermId`] sequence.
+pub(crate) mod vec {
+    use ontolius::TermId;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub(crate) fn serialize<S>(term_ids: &[TermId], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(term_ids.iter().map(ToString::to_string))
+    }
+
+    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<Vec<TermId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|curie| curie.parse().map_err(D::Error::custom))
+            .collect()
+    }
+}
@@ -0,0 +1,286 @@
+//! A module for semantic similarity of phenotype profiles.
+//!
+//! The similarity of two ontology terms is computed by a [`TermSimilarity`] measure:
+//!
+//! * [`Resnik`] - the information content of the most informative common ancestor (MICA),
+//! * [`Lin`] - the MICA information content normalized by the information content of the terms,
+//! * [`JiangConrath`] - the inverse of the Jiang–Conrath distance,
+//! * [`Jaccard`] - the overlap of the ancestor closures of the terms.
+//!
+//! The term similarities of the present features of two [`ObservableFeatures`] containers
+//! are combined into a profile similarity by [`profile_similarity`]
+//! with a [`ProfileAggregation`].
+//!
+//! ## Examples
+//!
+//! ```
+//! # let hpo = include!("../tests/common/toy_hpo.rs");
+//! use std::collections::HashMap;
+//! use ontolius::TermId;
+//! use phenotypes::FrequencyCategory;
+//! use phenotypes::simple::SimplePhenotypicFeature;
+//! use phenotypes::similarity::{
+//!     Jaccard, ProfileAggregation, Resnik, TermSimilarity, profile_similarity,
+//! };
+//!
+//! let postaxial: TermId = "HP:0100259".parse().unwrap();
+//! let preaxial: TermId = "HP:0100258".parse().unwrap();
+//! let seizure: TermId = "HP:0001250".parse().unwrap();
+//!
+//! let ic: HashMap<TermId, f64> = [
+//!     ("HP:0000001", 0.), ("HP:0000118", 0.), ("HP:0040064", 0.7),
+//!     ("HP:0010442", 1.2), ("HP:0100259", 2.3), ("HP:0100258", 2.3),
+//!     ("HP:0000707", 0.5), ("HP:0001250", 1.6),
+//! ].into_iter().map(|(curie, ic)| (curie.parse().unwrap(), ic)).collect();
+//!
+//! // `hpo` is a small hand-built ontology with the HPO terms used in the example.
+//! let resnik = Resnik::new(&hpo, &ic);
+//! // Polydactyly is the most informative common ancestor.
+//! assert_eq!(resnik.similarity(&postaxial, &preaxial), 1.2);
+//! assert_eq!(resnik.similarity(&postaxial, &seizure), 0.);
+//!
+//! let jaccard = Jaccard::new(&hpo);
+//! // The closures share 4 of 6 terms.
+//! assert!((jaccard.similarity(&postaxial, &preaxial) - 4. / 6.).abs() < 1e-12);
+//!
+//! let feature = |term_id: &TermId| SimplePhenotypicFeature::from_frequency_category(
+//!     term_id.clone(),
+//!     FrequencyCategory::Obligate,
+//! );
+//! let patient = vec![feature(&postaxial)];
+//! let disease = vec![feature(&preaxial), feature(&seizure)];
+//!
+//! let bma = profile_similarity(&patient, &disease, &resnik, ProfileAggregation::BestMatchAverage);
+//! assert_eq!(bma, 1.2);
+//!
+//! let symmetric = profile_similarity(
+//!     &patient,
+//!     &disease,
+//!     &resnik,
+//!     ProfileAggregation::SymmetricBestMatchAverage,
+//! );
+//! assert_eq!(symmetric, (1.2 + (1.2 + 0.) / 2.) / 2.);
+//! ```
+use std::collections::{HashMap, HashSet};
+
+use ontolius::ontology::HierarchyWalks;
+use ontolius::{Identified, TermId};
+
+use crate::ObservableFeatures;
+
+/// A source of the information content (IC) of ontology terms.
+pub trait TermInformationContent {
+    /// Get the information content of the term or `None` if the IC is not known.
+    fn information_content(&self, term_id: &TermId) -> Option<f64>;
+}
+
+impl TermInformationContent for HashMap<TermId, f64> {
+    fn information_content(&self, term_id: &TermId) -> Option<f64> {
+        self.get(term_id).copied()
+    }
+}
+
+/// A measure of the similarity of two ontology terms.
+pub trait TermSimilarity {
+    /// Compute the similarity of the terms `a` and `b`.
+    fn similarity(&self, a: &TermId, b: &TermId) -> f64;
+}
+
+/// Get the information content of the most informative common ancestor (MICA) of `a` and `b`.
+///
+/// The terms are considered to be their own ancestors and an unknown IC is treated as `0`.
+fn mica_ic<O, IC>(hierarchy: &O, ic: &IC, a: &TermId, b: &TermId) -> f64
+where
+    O: HierarchyWalks,
+    IC: TermInformationContent,
+{
+    let ancestors: HashSet<_> = hierarchy.iter_term_and_ancestor_ids(a).collect();
+    hierarchy
+        .iter_term_and_ancestor_ids(b)
+        .filter(|term_id| ancestors.contains(term_id))
+        .map(|term_id| ic.information_content(term_id).unwrap_or_default())
+        .fold(0., f64::max)
+}
+
+/// Resnik similarity is the information content of the most informative common ancestor of the terms.
+pub struct Resnik<'a, O, IC> {
+    hierarchy: &'a O,
+    ic: &'a IC,
+}
+
+impl<'a, O, IC> Resnik<'a, O, IC> {
+    pub fn new(hierarchy: &'a O, ic: &'a IC) -> Self {
+        Self { hierarchy, ic }
+    }
+}
+
+impl<O, IC> TermSimilarity for Resnik<'_, O, IC>
+where
+    O: HierarchyWalks,
+    IC: TermInformationContent,
+{
+    fn similarity(&self, a: &TermId, b: &TermId) -> f64 {
+        mica_ic(self.hierarchy, self.ic, a, b)
+    }
+}
+
+/// Lin similarity is the information content of the most informative common ancestor
+/// divided by the mean information content of the terms.
+///
+/// The similarity is in the `[0, 1]` range, and it is `0` if both terms have zero IC.
+pub struct Lin<'a, O, IC> {
+    hierarchy: &'a O,
+    ic: &'a IC,
+}
+
+impl<'a, O, IC> Lin<'a, O, IC> {
+    pub fn new(hierarchy: &'a O, ic: &'a IC) -> Self {
+        Self { hierarchy, ic }
+    }
+}
+
+impl<O, IC> TermSimilarity for Lin<'_, O, IC>
+where
+    O: HierarchyWalks,
+    IC: TermInformationContent,
+{
+    fn similarity(&self, a: &TermId, b: &TermId) -> f64 {
+        let denominator = self.ic.information_content(a).unwrap_or_default()
+            + self.ic.information_content(b).unwrap_or_default();
+        if denominator == 0. {
+            0.
+        } else {
+            2. * mica_ic(self.hierarchy, self.ic, a, b) / denominator
+        }
+    }
+}
+
+/// Jiang–Conrath similarity is `1 / (1 + d)`
+/// where `d = IC(a) + IC(b) - 2 IC(MICA)` is the Jiang–Conrath distance of the terms.
+///
+/// The similarity is in the `(0, 1]` range.
+pub struct JiangConrath<'a, O, IC> {
+    hierarchy: &'a O,
+    ic: &'a IC,
+}
+
+impl<'a, O, IC> JiangConrath<'a, O, IC> {
+    pub fn new(hierarchy: &'a O, ic: &'a IC) -> Self {
+        Self { hierarchy, ic }
+    }
+}
+
+impl<O, IC> TermSimilarity for JiangConrath<'_, O, IC>
+where
+    O: HierarchyWalks,
+    IC: TermInformationContent,
+{
+    fn similarity(&self, a: &TermId, b: &TermId) -> f64 {
+        let distance = self.ic.information_content(a).unwrap_or_default()
+            + self.ic.information_content(b).unwrap_or_default()
+            - 2. * mica_ic(self.hierarchy, self.ic, a, b);
+        1. / (1. + distance.max(0.))
+    }
+}
+
+/// Jaccard similarity is the size of the intersection divided by the size of the union
+/// of the ancestor closures of the terms.
+///
+/// The closure of a term includes the term and all its ancestors, hence the union is never empty.
+/// The closure of a term missing from the ontology includes just the term itself,
+/// so the similarity of a missing term is `1` to itself and `0` to any other term.
+///
+/// ```
+/// # let hpo = include!("../tests/common/toy_hpo.rs");
+/// use ontolius::TermId;
+/// use phenotypes::similarity::{Jaccard, TermSimilarity};
+///
+/// let seizure: TermId = "HP:0001250".parse().unwrap();
+/// let missing: TermId = "HP:9999999".parse().unwrap();
+///
+/// let jaccard = Jaccard::new(&hpo);
+/// assert_eq!(jaccard.similarity(&missing, &missing), 1.);
+/// assert_eq!(jaccard.similarity(&missing, &seizure), 0.);
+/// assert_eq!(jaccard.similarity(&seizure, &seizure), 1.);
+/// ```
+pub struct Jaccard<'a, O> {
+    hierarchy: &'a O,
+}
+
+impl<'a, O> Jaccard<'a, O> {
+    pub fn new(hierarchy: &'a O) -> Self {
+        Self { hierarchy }
+    }
+}
+
+impl<O> TermSimilarity for Jaccard<'_, O>
+where
+    O: HierarchyWalks,
+{
+    fn similarity(&self, a: &TermId, b: &TermId) -> f64 {
+        let a: HashSet<_> = self.hierarchy.iter_term_and_ancestor_ids(a).collect();
+        let b: HashSet<_> = self.hierarchy.iter_term_and_ancestor_ids(b).collect();
+        a.intersection(&b).count() as f64 / a.union(&b).count() as f64
+    }
+}
+
+/// The strategy for combining the term similarities into a profile similarity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProfileAggregation {
+    /// The maximum similarity of all term pairs.
+    Max,
+    /// The average of the best matches of the query terms in the target.
+    ///
+    /// The aggregation is asymmetric.
+    BestMatchAverage,
+    /// The mean of the best match averages computed in both directions.
+    SymmetricBestMatchAverage,
+}
+
+/// Compute the similarity of the present features of the `query` and the `target`.
+///
+/// The similarity is `0` if either container has no present features.
+pub fn profile_similarity<A, B, S>(
+    query: &A,
+    target: &B,
+    measure: &S,
+    aggregation: ProfileAggregation,
+) -> f64
+where
+    A: ObservableFeatures,
+    A::Feature: Identified,
+    B: ObservableFeatures,
+    B::Feature: Identified,
+    S: TermSimilarity,
+{
+    let query: Vec<_> = query.present_features().map(|f| f.identifier()).collect();
+    let target: Vec<_> = target.present_features().map(|f| f.identifier()).collect();
+    if query.is_empty() || target.is_empty() {
+        return 0.;
+    }
+
+    let scores: Vec<Vec<f64>> = query
+        .iter()
+        .map(|a| target.iter().map(|b| measure.similarity(a, b)).collect())
+        .collect();
+
+    match aggregation {
+        ProfileAggregation::Max => scores.iter().flatten().copied().fold(0., f64::max),
+        ProfileAggregation::BestMatchAverage => best_match_average(&scores),
+        ProfileAggregation::SymmetricBestMatchAverage => {
+            let transposed: Vec<Vec<f64>> = (0..target.len())
+                .map(|j| scores.iter().map(|row| row[j]).collect())
+                .collect();
+            (best_match_average(&scores) + best_match_average(&transposed)) / 2.
+        }
+    }
+}
+
+fn best_match_average(scores: &[Vec<f64>]) -> f64 {
+    let total: f64 = scores
+        .iter()
+        .map(|row| row.iter().copied().fold(f64::NEG_INFINITY, f64::max))
+        .sum();
+    total / scores.len() as f64
+}
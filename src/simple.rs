@@ -1,21 +1,158 @@
 //! An experimental module with example implementations.
+use std::collections::HashMap;
+
 use ontolius::{Identified, TermId};
 
-use crate::{Fraction, Observable};
+use crate::hpoa::{Aspect, DiseaseAnnotations, HpoaRecord};
+use crate::modifiers::{ModifierAware, Modifiers};
+use crate::temporal::{Age, TemporalObservable, TimeElement};
+use crate::{
+    Fraction, Frequency, FrequencyAware, FrequencyCategory, Observable, ObservableFeatures,
+    ObservationState, PhenotypesError, Sex,
+};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// A phenotypic feature annotated with its [`Frequency`], if known.
+///
+/// The feature can be built from either HPO annotation style:
+///
+/// ```
+/// use ontolius::TermId;
+/// use phenotypes::{Fraction, FrequencyAware, FrequencyCategory, Observable};
+/// use phenotypes::simple::SimplePhenotypicFeature;
+///
+/// let polydactyly: TermId = "HP:0010442".parse().unwrap();
+///
+/// let a = SimplePhenotypicFeature::new(polydactyly.clone(), Fraction::try_from((3, 10)).unwrap());
+/// assert!(a.is_present());
+///
+/// let b = SimplePhenotypicFeature::from_frequency_category(polydactyly.clone(), FrequencyCategory::Excluded);
+/// assert!(b.is_excluded());
+/// assert_eq!(b.probability(), Some(0.));
+///
+/// let c = SimplePhenotypicFeature::without_frequency(polydactyly);
+/// assert!(c.is_present());
+/// assert_eq!(c.frequency(), None);
+/// assert_eq!(c.probability(), None);
+/// ```
+///
+/// With the `serde` feature, the term ID is (de)serialized as a CURIE:
+///
+/// ```
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use phenotypes::Fraction;
+/// use phenotypes::simple::SimplePhenotypicFeature;
+///
+/// let feature = SimplePhenotypicFeature::new(
+///     "HP:0010442".parse().unwrap(),
+///     Fraction::try_from((3, 10)).unwrap(),
+/// );
+///
+/// let json = serde_json::to_string(&feature).unwrap();
+/// assert_eq!(json, r#"{"identifier":"HP:0010442","frequency":{"Fraction":{"n":3,"m":10}}}"#);
+///
+/// let other: SimplePhenotypicFeature = serde_json::from_str(&json).unwrap();
+/// assert_eq!(feature, other);
+/// # }
+/// ```
+///
+/// The feature can have an onset and a resolution:
+///
+/// ```
+/// use phenotypes::Fraction;
+/// use phenotypes::simple::SimplePhenotypicFeature;
+/// use phenotypes::temporal::{TemporalObservable, TimeElement};
+///
+/// let congenital = TimeElement::OntologyClass("HP:0003577".parse().unwrap());
+/// let feature = SimplePhenotypicFeature::new(
+///     "HP:0010442".parse().unwrap(),
+///     Fraction::try_from((1u32, 1)).unwrap(),
+/// ).with_onset(congenital.clone());
+///
+/// assert_eq!(feature.onset(), Some(&congenital));
+/// assert_eq!(feature.resolution(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SimplePhenotypicFeature {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
     identifier: TermId,
-    fraction: Fraction,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    frequency: Option<Frequency>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    onset: Option<TimeElement>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    resolution: Option<TimeElement>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Modifiers::is_empty")
+    )]
+    modifiers: Modifiers,
 }
 
 impl SimplePhenotypicFeature {
     pub fn new(identifier: TermId, fraction: Fraction) -> Self {
         Self {
             identifier,
-            fraction,
+            frequency: Some(Frequency::Fraction(fraction)),
+            onset: None,
+            resolution: None,
+            modifiers: Modifiers::default(),
         }
     }
+
+    pub fn from_frequency_category(identifier: TermId, category: FrequencyCategory) -> Self {
+        Self {
+            identifier,
+            frequency: Some(Frequency::Category(category)),
+            onset: None,
+            resolution: None,
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    /// Create a present feature whose frequency is not known.
+    pub fn without_frequency(identifier: TermId) -> Self {
+        Self {
+            identifier,
+            frequency: None,
+            onset: None,
+            resolution: None,
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    /// Set the onset of the feature.
+    pub fn with_onset(mut self, onset: TimeElement) -> Self {
+        self.onset = Some(onset);
+        self
+    }
+
+    /// Set the resolution of the feature.
+    pub fn with_resolution(mut self, resolution: TimeElement) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+
+    /// Set the modifiers of the feature, such as its severity.
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    /// Get the frequency of the feature or `None` if the frequency is not known.
+    pub fn frequency(&self) -> Option<&Frequency> {
+        self.frequency.as_ref()
+    }
 }
 
 impl Identified for SimplePhenotypicFeature {
@@ -24,8 +161,625 @@ impl Identified for SimplePhenotypicFeature {
     }
 }
 
+impl FrequencyAware for SimplePhenotypicFeature {
+    fn probability(&self) -> Option<f64> {
+        self.frequency.as_ref().and_then(Frequency::probability)
+    }
+}
+
+/// The feature annotated with a fraction is present if observed in at least one item,
+/// excluded if investigated in at least one item but never observed,
+/// and unknown if not investigated at all (`0/0`).
+///
+/// The feature annotated with a frequency category is excluded
+/// if the category is [`FrequencyCategory::Excluded`] and present otherwise.
+/// Similarly, the feature annotated with a percentage is excluded if the percentage is zero.
+/// The feature with an unknown frequency is present.
 impl Observable for SimplePhenotypicFeature {
-    fn is_present(&self) -> bool {
-        self.fraction.n() > 0
+    fn observation_state(&self) -> ObservationState {
+        let Some(frequency) = &self.frequency else {
+            return ObservationState::Present;
+        };
+        match frequency {
+            Frequency::Fraction(fraction) => {
+                if fraction.n() > 0 {
+                    ObservationState::Present
+                } else if fraction.m() > 0 {
+                    ObservationState::Excluded
+                } else {
+                    ObservationState::Unknown
+                }
+            }
+            Frequency::Category(FrequencyCategory::Excluded) => ObservationState::Excluded,
+            Frequency::Category(_) => ObservationState::Present,
+            Frequency::Percentage(percentage) => {
+                if *percentage == 0. {
+                    ObservationState::Excluded
+                } else {
+                    ObservationState::Present
+                }
+            }
+        }
+    }
+}
+
+impl ModifierAware for SimplePhenotypicFeature {
+    fn modifiers(&self) -> &Modifiers {
+        &self.modifiers
+    }
+}
+
+impl TemporalObservable for SimplePhenotypicFeature {
+    fn onset(&self) -> Option<&TimeElement> {
+        self.onset.as_ref()
+    }
+
+    fn resolution(&self) -> Option<&TimeElement> {
+        self.resolution.as_ref()
+    }
+}
+
+/// A phenotypic feature observed or excluded in a single individual.
+///
+/// ```
+/// use phenotypes::Observable;
+/// use phenotypes::modifiers::{Modifiers, Severity};
+/// use phenotypes::simple::SimpleObservedFeature;
+///
+/// let seizure = SimpleObservedFeature::present("HP:0001250".parse().unwrap())
+///     .with_modifiers(Modifiers::default().with_severity(Severity::Severe));
+/// assert!(seizure.is_present());
+///
+/// let polydactyly = SimpleObservedFeature::excluded("HP:0010442".parse().unwrap());
+/// assert!(polydactyly.is_excluded());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimpleObservedFeature {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
+    identifier: TermId,
+    state: ObservationState,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    onset: Option<TimeElement>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    resolution: Option<TimeElement>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Modifiers::is_empty")
+    )]
+    modifiers: Modifiers,
+}
+
+impl SimpleObservedFeature {
+    pub fn new(identifier: TermId, state: ObservationState) -> Self {
+        Self {
+            identifier,
+            state,
+            onset: None,
+            resolution: None,
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    /// Create a feature observed in the individual.
+    pub fn present(identifier: TermId) -> Self {
+        Self::new(identifier, ObservationState::Present)
+    }
+
+    /// Create a feature whose presence was excluded in the individual.
+    pub fn excluded(identifier: TermId) -> Self {
+        Self::new(identifier, ObservationState::Excluded)
+    }
+
+    /// Set the onset of the feature.
+    pub fn with_onset(mut self, onset: TimeElement) -> Self {
+        self.onset = Some(onset);
+        self
+    }
+
+    /// Set the resolution of the feature.
+    pub fn with_resolution(mut self, resolution: TimeElement) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+
+    /// Set the modifiers of the feature, such as its severity.
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+}
+
+impl Identified for SimpleObservedFeature {
+    fn identifier(&self) -> &TermId {
+        &self.identifier
+    }
+}
+
+impl Observable for SimpleObservedFeature {
+    fn observation_state(&self) -> ObservationState {
+        self.state
+    }
+}
+
+impl ModifierAware for SimpleObservedFeature {
+    fn modifiers(&self) -> &Modifiers {
+        &self.modifiers
+    }
+}
+
+impl TemporalObservable for SimpleObservedFeature {
+    fn onset(&self) -> Option<&TimeElement> {
+        self.onset.as_ref()
+    }
+
+    fn resolution(&self) -> Option<&TimeElement> {
+        self.resolution.as_ref()
+    }
+}
+
+/// The vital status of an individual.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VitalStatus {
+    /// The vital status is not known.
+    #[default]
+    Unknown,
+    /// The individual is alive.
+    Alive,
+    /// The individual died, possibly at a known age.
+    Deceased { age_of_death: Option<Age> },
+}
+
+/// An individual, such as a patient, with the observed features, diseases, and variants.
+///
+/// ## Examples
+///
+/// ```
+/// use ontolius::{Identified, TermId};
+/// use phenotypes::{ObservableFeatures, Sex};
+/// use phenotypes::simple::{SimpleIndividual, SimpleObservedFeature, VitalStatus};
+///
+/// let patient = SimpleIndividual::new("PATIENT:1".parse().unwrap(), Sex::Female)
+///     .with_age("P3Y".parse().unwrap())
+///     .with_vital_status(VitalStatus::Alive)
+///     .with_feature(SimpleObservedFeature::present("HP:0001250".parse().unwrap()))
+///     .with_feature(SimpleObservedFeature::excluded("HP:0010442".parse().unwrap()))
+///     .with_disease("OMIM:619340".parse().unwrap())
+///     .with_variant("NM_001848.3:c.877G>A");
+///
+/// assert_eq!(patient.identifier().to_string(), "PATIENT:1");
+/// assert_eq!(patient.sex(), Sex::Female);
+/// assert_eq!(patient.present_feature_count(), 1);
+/// assert_eq!(patient.excluded_feature_count(), 1);
+/// assert_eq!(patient.diseases().len(), 1);
+/// assert_eq!(patient.variants(), ["NM_001848.3:c.877G>A"]);
+/// ```
+///
+/// The individual can be passed to the algorithms of the crate,
+/// e.g. to count the features of a cohort:
+///
+/// ```
+/// use ontolius::TermId;
+/// use phenotypes::{Fraction, Sex};
+/// use phenotypes::cohort::FeatureFrequencies;
+/// use phenotypes::simple::{SimpleIndividual, SimpleObservedFeature};
+///
+/// let seizure: TermId = "HP:0001250".parse().unwrap();
+/// let cohort = [
+///     SimpleIndividual::new("PATIENT:1".parse().unwrap(), Sex::Female)
+///         .with_feature(SimpleObservedFeature::present(seizure.clone())),
+///     SimpleIndividual::new("PATIENT:2".parse().unwrap(), Sex::Male)
+///         .with_feature(SimpleObservedFeature::excluded(seizure.clone())),
+/// ];
+///
+/// let frequencies = FeatureFrequencies::from_items(&cohort);
+/// assert_eq!(frequencies.get(&seizure), Some(&Fraction::try_from((1u32, 2)).unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimpleIndividual {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
+    identifier: TermId,
+    sex: Sex,
+    age: Option<Age>,
+    vital_status: VitalStatus,
+    features: Vec<SimpleObservedFeature>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie::vec"))]
+    diseases: Vec<TermId>,
+    variants: Vec<String>,
+}
+
+impl SimpleIndividual {
+    pub fn new(identifier: TermId, sex: Sex) -> Self {
+        Self {
+            identifier,
+            sex,
+            age: None,
+            vital_status: VitalStatus::default(),
+            features: vec![],
+            diseases: vec![],
+            variants: vec![],
+        }
+    }
+
+    /// Set the age of the individual at the last encounter.
+    pub fn with_age(mut self, age: Age) -> Self {
+        self.age = Some(age);
+        self
+    }
+
+    /// Set the vital status.
+    pub fn with_vital_status(mut self, vital_status: VitalStatus) -> Self {
+        self.vital_status = vital_status;
+        self
+    }
+
+    /// Add an observed or excluded feature.
+    pub fn with_feature(mut self, feature: SimpleObservedFeature) -> Self {
+        self.features.push(feature);
+        self
+    }
+
+    /// Add the ID of a diagnosed disease, e.g. `OMIM:619340`.
+    pub fn with_disease(mut self, disease_id: TermId) -> Self {
+        self.diseases.push(disease_id);
+        self
+    }
+
+    /// Add a variant, e.g. in the HGVS notation.
+    pub fn with_variant(mut self, variant: impl Into<String>) -> Self {
+        self.variants.push(variant.into());
+        self
+    }
+
+    /// Get the sex.
+    pub fn sex(&self) -> Sex {
+        self.sex
+    }
+
+    /// Get the age at the last encounter.
+    pub fn age(&self) -> Option<&Age> {
+        self.age.as_ref()
+    }
+
+    /// Get the vital status.
+    pub fn vital_status(&self) -> VitalStatus {
+        self.vital_status
+    }
+
+    /// Get all features, regardless of their observation state.
+    pub fn features(&self) -> &[SimpleObservedFeature] {
+        &self.features
+    }
+
+    /// Get the IDs of the diagnosed diseases.
+    pub fn diseases(&self) -> &[TermId] {
+        &self.diseases
+    }
+
+    /// Get the variants.
+    pub fn variants(&self) -> &[String] {
+        &self.variants
+    }
+}
+
+impl Identified for SimpleIndividual {
+    fn identifier(&self) -> &TermId {
+        &self.identifier
+    }
+}
+
+impl ObservableFeatures for SimpleIndividual {
+    type Feature = SimpleObservedFeature;
+
+    fn present_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        self.features.iter().filter(|f| f.is_present())
+    }
+
+    fn excluded_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        self.features.iter().filter(|f| f.is_excluded())
+    }
+
+    fn unknown_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        self.features.iter().filter(|f| f.is_unknown())
     }
 }
+
+/// A disease with its phenotypic features, onset, and modes of inheritance.
+///
+/// ## Examples
+///
+/// ```
+/// use ontolius::Identified;
+/// use phenotypes::{Fraction, FrequencyCategory, ObservableFeatures};
+/// use phenotypes::simple::{SimpleDisease, SimplePhenotypicFeature};
+/// use phenotypes::temporal::TimeElement;
+///
+/// let disease = SimpleDisease::new("OMIM:619340".parse().unwrap(), "DEE 96")
+///     .with_feature(SimplePhenotypicFeature::new(
+///         "HP:0001250".parse().unwrap(),
+///         Fraction::try_from((3u32, 4)).unwrap(),
+///     ))
+///     .with_feature(SimplePhenotypicFeature::from_frequency_category(
+///         "HP:0010442".parse().unwrap(),
+///         FrequencyCategory::Excluded,
+///     ))
+///     .with_onset(TimeElement::OntologyClass("HP:0003593".parse().unwrap()))
+///     .with_inheritance("HP:0000006".parse().unwrap());
+///
+/// assert_eq!(disease.identifier().to_string(), "OMIM:619340");
+/// assert_eq!(disease.name(), "DEE 96");
+/// assert_eq!(disease.present_feature_count(), 1);
+/// assert_eq!(disease.excluded_feature_count(), 1);
+/// assert_eq!(disease.inheritance().len(), 1);
+/// ```
+///
+/// The disease can be built from the HPO annotations:
+///
+/// ```
+/// use phenotypes::{Fraction, Frequency, ObservableFeatures};
+/// use phenotypes::hpoa::HpoaReader;
+/// use phenotypes::simple::SimpleDisease;
+///
+/// let hpoa = "\
+/// OMIM:619340\tDEE 96\t\tHP:0011097\tPMID:31675180\tPCS\t\t1/2\t\t\tP\tHPO:probinson[2021-06-21]
+/// OMIM:619340\tDEE 96\t\tHP:0011097\tPMID:33098801\tPCS\t\t2/3\t\t\tP\tHPO:probinson[2021-06-21]
+/// OMIM:619340\tDEE 96\t\tHP:0001249\tPMID:31675180\tPCS\t\t\t\t\tP\tHPO:probinson[2021-06-21]
+/// OMIM:619340\tDEE 96\tNOT\tHP:0001250\tPMID:31675180\tPCS\t\t\t\t\tP\tHPO:probinson[2021-06-21]
+/// OMIM:619340\tDEE 96\t\tHP:0000006\tPMID:31675180\tPCS\t\t\t\t\tI\tHPO:probinson[2021-06-21]
+/// OMIM:619340\tDEE 96\t\tHP:0003593\tPMID:31675180\tPCS\t\t\t\t\tC\tHPO:probinson[2021-06-21]
+/// ";
+///
+/// let annotations = HpoaReader::new(hpoa.as_bytes())
+///     .diseases()
+///     .next()
+///     .unwrap()
+///     .unwrap();
+/// let disease = SimpleDisease::try_from(&annotations).unwrap();
+///
+/// assert_eq!(disease.name(), "DEE 96");
+/// assert_eq!(disease.features().len(), 3);
+/// assert_eq!(disease.present_feature_count(), 2);
+/// assert_eq!(disease.excluded_feature_count(), 1);
+///
+/// // The fractions of the same feature are summed up.
+/// let feature = &disease.features()[0];
+/// assert_eq!(feature.frequency(), Some(&Frequency::Fraction(Fraction::try_from((3, 5)).unwrap())));
+///
+/// // The frequency of a feature annotated without a frequency is unknown.
+/// let feature = &disease.features()[1];
+/// assert_eq!(feature.frequency(), None);
+///
+/// assert_eq!(disease.inheritance()[0].to_string(), "HP:0000006");
+/// assert_eq!(disease.onset().len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimpleDisease {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
+    identifier: TermId,
+    name: String,
+    features: Vec<SimplePhenotypicFeature>,
+    onset: Vec<TimeElement>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie::vec"))]
+    inheritance: Vec<TermId>,
+}
+
+impl SimpleDisease {
+    pub fn new(identifier: TermId, name: impl Into<String>) -> Self {
+        Self {
+            identifier,
+            name: name.into(),
+            features: vec![],
+            onset: vec![],
+            inheritance: vec![],
+        }
+    }
+
+    /// Add a phenotypic feature.
+    pub fn with_feature(mut self, feature: SimplePhenotypicFeature) -> Self {
+        self.features.push(feature);
+        self
+    }
+
+    /// Add an onset of the disease.
+    pub fn with_onset(mut self, onset: TimeElement) -> Self {
+        self.onset.push(onset);
+        self
+    }
+
+    /// Add a mode of inheritance, e.g. [Autosomal dominant inheritance (HP:0000006)](https://hpo.jax.org/browse/term/HP:0000006).
+    pub fn with_inheritance(mut self, inheritance: TermId) -> Self {
+        self.inheritance.push(inheritance);
+        self
+    }
+
+    /// Get the name of the disease.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get all features, regardless of their observation state.
+    pub fn features(&self) -> &[SimplePhenotypicFeature] {
+        &self.features
+    }
+
+    /// Get the onsets of the disease.
+    pub fn onset(&self) -> &[TimeElement] {
+        &self.onset
+    }
+
+    /// Get the modes of inheritance.
+    pub fn inheritance(&self) -> &[TermId] {
+        &self.inheritance
+    }
+}
+
+impl Identified for SimpleDisease {
+    fn identifier(&self) -> &TermId {
+        &self.identifier
+    }
+}
+
+impl ObservableFeatures for SimpleDisease {
+    type Feature = SimplePhenotypicFeature;
+
+    fn present_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        self.features.iter().filter(|f| f.is_present())
+    }
+
+    fn excluded_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        self.features.iter().filter(|f| f.is_excluded())
+    }
+
+    fn unknown_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        self.features.iter().filter(|f| f.is_unknown())
+    }
+}
+
+/// Build the disease from its HPO annotations.
+///
+/// The phenotypic abnormality records of the same HPO term are merged into a single feature,
+/// in the order of the first appearance:
+///
+/// * the fractions of the records that are not negated are summed up,
+/// * a feature without fractions gets the frequency category or the percentage
+///   of the first record that is not negated, and the frequency is unknown
+///   if no such record has a frequency,
+/// * a feature with all records negated is excluded.
+///
+/// The percentages are never summed up with the fractions, since they carry no counts.
+/// The onset and the modifiers of a feature are taken from its records that are not negated.
+/// The clinical course records with onset terms make up the onset of the disease
+/// and the inheritance records make up the modes of inheritance.
+///
+/// Fails with [`PhenotypesError::CountOutOfRange`] if the summed up counts overflow.
+///
+/// ## Examples
+///
+/// ```
+/// use phenotypes::{Fraction, Frequency, Observable, PhenotypesError};
+/// use phenotypes::hpoa::HpoaReader;
+/// use phenotypes::modifiers::ModifierAware;
+/// use phenotypes::simple::SimpleDisease;
+/// use phenotypes::temporal::TemporalObservable;
+///
+/// let hpoa = "\
+/// OMIM:619340\tDEE 96\t\tHP:0001250\tPMID:31675180\tPCS\t\t17%\t\t\tP\tHPO:probinson[2021-06-21]
+/// OMIM:619340\tDEE 96\tNOT\tHP:0001250\tPMID:33098801\tPCS\tHP:0003577\t\t\tHP:0012828\tP\tHPO:probinson[2021-06-21]
+/// OMIM:619340\tDEE 96\t\tHP:0001250\tPMID:35000000\tPCS\t\t3/4\t\t\tP\tHPO:probinson[2021-06-21]
+/// OMIM:619340\tDEE 96\t\tHP:0011097\tPMID:31675180\tPCS\t\t40%\t\t\tP\tHPO:probinson[2021-06-21]
+/// ";
+/// let annotations = HpoaReader::new(hpoa.as_bytes()).diseases().next().unwrap().unwrap();
+/// let disease = SimpleDisease::try_from(&annotations).unwrap();
+///
+/// // The percentage is not summed up with the fraction as if it was 17/100.
+/// let seizure = &disease.features()[0];
+/// let three_of_four = Fraction::try_from((3u32, 4)).unwrap();
+/// assert_eq!(seizure.frequency(), Some(&Frequency::Fraction(three_of_four)));
+///
+/// // The onset and the modifiers of the negated record are not used.
+/// assert!(seizure.is_present());
+/// assert_eq!(seizure.onset(), None);
+/// assert!(seizure.modifiers().is_empty());
+///
+/// // The percentage is kept if there are no fractions.
+/// let ictal = &disease.features()[1];
+/// assert_eq!(ictal.frequency(), Some(&Frequency::Percentage(40.)));
+///
+/// // The overflow of the summed up counts is an error.
+/// let line = "OMIM:619340\tDEE 96\t\tHP:0001250\tPMID:31675180\tPCS\t\t1/4294967295\t\t\tP\tHPO:probinson[2021-06-21]\n";
+/// let hpoa = line.repeat(2);
+/// let annotations = HpoaReader::new(hpoa.as_bytes()).diseases().next().unwrap().unwrap();
+/// assert_eq!(
+///     SimpleDisease::try_from(&annotations),
+///     Err(PhenotypesError::CountOutOfRange { value: "8589934590".to_string() }),
+/// );
+/// ```
+impl TryFrom<&DiseaseAnnotations> for SimpleDisease {
+    type Error = PhenotypesError;
+
+    fn try_from(value: &DiseaseAnnotations) -> Result<Self, Self::Error> {
+        let mut disease = SimpleDisease::new(value.disease_id().clone(), value.disease_name());
+
+        let mut grouped: Vec<(&TermId, Vec<&HpoaRecord>)> = vec![];
+        let mut index: HashMap<&TermId, usize> = HashMap::new();
+        for record in value.records_with_aspect(Aspect::PhenotypicAbnormality) {
+            let i = *index.entry(record.hpo_id()).or_insert_with(|| {
+                grouped.push((record.hpo_id(), vec![]));
+                grouped.len() - 1
+            });
+            grouped[i].1.push(record);
+        }
+        disease.features = grouped
+            .into_iter()
+            .map(|(term_id, records)| merge_records(term_id, &records))
+            .collect::<Result<_, _>>()?;
+
+        for record in value.records_with_aspect(Aspect::ClinicalCourse) {
+            let onset = TimeElement::OntologyClass(record.hpo_id().clone());
+            if !record.is_negated() && onset.day_range().is_some() {
+                disease.onset.push(onset);
+            }
+        }
+        disease.inheritance = value
+            .records_with_aspect(Aspect::Inheritance)
+            .filter(|record| !record.is_negated())
+            .map(|record| record.hpo_id().clone())
+            .collect();
+
+        Ok(disease)
+    }
+}
+
+/// Merge the annotation records of the same term into a feature.
+fn merge_records(
+    term_id: &TermId,
+    records: &[&HpoaRecord],
+) -> Result<SimplePhenotypicFeature, PhenotypesError> {
+    let asserted: Vec<_> = records.iter().filter(|r| !r.is_negated()).collect();
+
+    let mut fraction: Option<Fraction> = None;
+    for record in &asserted {
+        if let Some(Frequency::Fraction(f)) = record.frequency() {
+            fraction = Some(match fraction {
+                Some(total) => {
+                    total
+                        .checked_add(f)
+                        .ok_or_else(|| PhenotypesError::CountOutOfRange {
+                            value: (u64::from(total.m()) + u64::from(f.m())).to_string(),
+                        })?
+                }
+                None => f.clone(),
+            });
+        }
+    }
+    let frequency = match fraction {
+        Some(fraction) => Some(Frequency::Fraction(fraction)),
+        None if asserted.is_empty() => Some(Frequency::Category(FrequencyCategory::Excluded)),
+        None => asserted.iter().find_map(|r| match r.frequency() {
+            Some(frequency @ (Frequency::Category(_) | Frequency::Percentage(_))) => {
+                Some(frequency.clone())
+            }
+            _ => None,
+        }),
+    };
+
+    Ok(SimplePhenotypicFeature {
+        identifier: term_id.clone(),
+        frequency,
+        onset: asserted
+            .iter()
+            .find_map(|r| r.onset())
+            .map(|onset| TimeElement::OntologyClass(onset.clone())),
+        resolution: None,
+        modifiers: Modifiers::from_term_ids(asserted.iter().flat_map(|r| r.modifiers()).cloned()),
+    })
+}
@@ -0,0 +1,282 @@
+//! Numerical routines shared by the statistical APIs of the crate.
+
+use std::f64::consts::PI;
+
+/// Lanczos approximation coefficients (g = 7, n = 9).
+const LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// Compute the natural logarithm of the gamma function for `x > 0`.
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    if x < 0.5 {
+        // Reflection formula.
+        (PI / (PI * x).sin()).ln() - ln_gamma(1. - x)
+    } else {
+        let x = x - 1.;
+        let t = x + 7.5;
+        let mut a = LANCZOS[0];
+        for (i, &c) in LANCZOS.iter().enumerate().skip(1) {
+            a += c / (x + i as f64);
+        }
+        0.5 * (2. * PI).ln() + (x + 0.5) * t.ln() - t + a.ln()
+    }
+}
+
+/// Compute the natural logarithm of the beta function.
+pub(crate) fn ln_beta(a: f64, b: f64) -> f64 {
+    ln_gamma(a) + ln_gamma(b) - ln_gamma(a + b)
+}
+
+/// Evaluate the continued fraction of the incomplete beta function
+/// using the modified Lentz's method.
+fn beta_continued_fraction(x: f64, a: f64, b: f64) -> f64 {
+    const MAX_ITER: usize = 500;
+    const EPS: f64 = 1e-15;
+    const TINY: f64 = 1e-300;
+
+    let qab = a + b;
+    let qap = a + 1.;
+    let qam = a - 1.;
+    let mut c = 1.;
+    let mut d = 1. - qab * x / qap;
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1. / d;
+    let mut h = d;
+    for m in 1..=MAX_ITER {
+        let m = m as f64;
+        let m2 = 2. * m;
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1. + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1. + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1. / d;
+        h *= d * c;
+
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1. + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1. + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1. / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.).abs() < EPS {
+            break;
+        }
+    }
+    h
+}
+
+/// Compute the regularized incomplete beta function *I_x(a, b)*,
+/// i.e. the CDF of the Beta(a, b) distribution at `x`.
+pub(crate) fn beta_cdf(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0. {
+        return 0.;
+    }
+    if x >= 1. {
+        return 1.;
+    }
+    let ln_front = a * x.ln() + b * (1. - x).ln() - ln_beta(a, b);
+    if x < (a + 1.) / (a + b + 2.) {
+        ln_front.exp() * beta_continued_fraction(x, a, b) / a
+    } else {
+        1. - ln_front.exp() * beta_continued_fraction(1. - x, b, a) / b
+    }
+}
+
+/// Compute the quantile (inverse CDF) of the Beta(a, b) distribution.
+pub(crate) fn beta_quantile(p: f64, a: f64, b: f64) -> f64 {
+    if p <= 0. {
+        return 0.;
+    }
+    if p >= 1. {
+        return 1.;
+    }
+    // The CDF is monotonic, hence bisection always converges.
+    let (mut lo, mut hi) = (0f64, 1f64);
+    for _ in 0..200 {
+        let mid = 0.5 * (lo + hi);
+        if beta_cdf(mid, a, b) < p {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+        if hi - lo < 1e-15 {
+            break;
+        }
+    }
+    0.5 * (lo + hi)
+}
+
+/// Compute the quantile (inverse CDF) of the standard normal distribution
+/// using the algorithm AS 241 by Wichura (1988).
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    if p <= 0. {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1. {
+        return f64::INFINITY;
+    }
+    let q = p - 0.5;
+    if q.abs() <= 0.425 {
+        let r = 0.180625 - q * q;
+        return q
+            * (((((((2_509.080_928_730_122_7 * r + 33_430.575_583_588_13) * r
+                + 67_265.770_927_008_7)
+                * r
+                + 45_921.953_931_549_87)
+                * r
+                + 13_731.693_765_509_46)
+                * r
+                + 1_971.590_950_306_551_3)
+                * r
+                + 133.141_667_891_784_38)
+                * r
+                + 3.387_132_872_796_366_5)
+            / (((((((5_226.495_278_852_545 * r + 28_729.085_735_721_943) * r
+                + 39_307.895_800_092_71)
+                * r
+                + 21_213.794_301_586_597)
+                * r
+                + 5_394.196_021_424_751)
+                * r
+                + 687.187_007_492_057_9)
+                * r
+                + 42.313_330_701_600_91)
+                * r
+                + 1.);
+    }
+    let r = if q < 0. { p } else { 1. - p };
+    let r = (-r.ln()).sqrt();
+    let val = if r <= 5. {
+        let r = r - 1.6;
+        (((((((7.745_450_142_783_414e-4 * r + 0.022_723_844_989_269_184) * r
+            + 0.241_780_725_177_450_6)
+            * r
+            + 1.270_458_252_452_368_4)
+            * r
+            + 3.647_848_324_763_204_5)
+            * r
+            + 5.769_497_221_460_691)
+            * r
+            + 4.630_337_846_156_546)
+            * r
+            + 1.423_437_110_749_683_5)
+            / (((((((1.050_750_071_644_416_9e-9 * r + 5.475_938_084_995_345e-4) * r
+                + 0.015_198_666_563_616_457)
+                * r
+                + 0.148_103_976_427_480_08)
+                * r
+                + 0.689_767_334_985_1)
+                * r
+                + 1.676_384_830_183_803_8)
+                * r
+                + 2.053_191_626_637_759)
+                * r
+                + 1.)
+    } else {
+        let r = r - 5.;
+        (((((((2.010_334_399_292_288_1e-7 * r + 2.711_555_568_743_487_6e-5) * r
+            + 1.242_660_947_388_078_4e-3)
+            * r
+            + 0.026_532_189_526_576_124)
+            * r
+            + 0.296_560_571_828_504_9)
+            * r
+            + 1.784_826_539_917_291_3)
+            * r
+            + 5.463_784_911_164_114)
+            * r
+            + 6.657_904_643_501_103)
+            / (((((((2.044_263_103_389_939_7e-15 * r + 1.421_511_758_316_446e-7) * r
+                + 1.846_318_317_510_054_8e-5)
+                * r
+                + 7.868_691_311_456_133e-4)
+                * r
+                + 0.014_875_361_290_850_615)
+                * r
+                + 0.136_929_880_922_735_8)
+                * r
+                + 0.599_832_206_555_888)
+                * r
+                + 1.)
+    };
+    if q < 0. { -val } else { val }
+}
+
+/// Compute the regularized upper incomplete gamma function *Q(a, x)*.
+pub(crate) fn gamma_q(a: f64, x: f64) -> f64 {
+    const MAX_ITER: usize = 500;
+    const EPS: f64 = 1e-15;
+    const TINY: f64 = 1e-300;
+
+    if x <= 0. {
+        return 1.;
+    }
+    let ln_front = a * x.ln() - x - ln_gamma(a);
+    if x < a + 1. {
+        // Series representation of P(a, x).
+        let mut term = 1. / a;
+        let mut sum = term;
+        let mut ap = a;
+        for _ in 0..MAX_ITER {
+            ap += 1.;
+            term *= x / ap;
+            sum += term;
+            if term.abs() < sum.abs() * EPS {
+                break;
+            }
+        }
+        1. - sum * ln_front.exp()
+    } else {
+        // Continued fraction representation of Q(a, x) using the modified Lentz's method.
+        let mut b = x + 1. - a;
+        let mut c = 1. / TINY;
+        let mut d = 1. / b;
+        let mut h = d;
+        for i in 1..=MAX_ITER {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.;
+            d = an * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + an / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1. / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.).abs() < EPS {
+                break;
+            }
+        }
+        ln_front.exp() * h
+    }
+}
+
+/// Compute the natural logarithm of the binomial coefficient *n choose k*.
+pub(crate) fn ln_choose(n: f64, k: f64) -> f64 {
+    ln_gamma(n + 1.) - ln_gamma(k + 1.) - ln_gamma(n - k + 1.)
+}
@@ -0,0 +1,415 @@
+//! A module for the temporal aspects of observations, such as the onset and the resolution
+//! of a phenotypic feature.
+//!
+//! A [`TimeElement`] is an ISO 8601 [`Age`], a [`GestationalAge`],
+//! an HPO [Onset (HP:0003674)](https://hpo.jax.org/browse/term/HP:0003674) term,
+//! or an age range. All time elements are mapped onto a common time axis
+//! of days since birth, where the prenatal ages are negative.
+//!
+//! ## Examples
+//!
+//! ```
+//! use phenotypes::Fraction;
+//! use phenotypes::simple::SimplePhenotypicFeature;
+//! use phenotypes::temporal::{Age, TemporalFeatures, TimeElement};
+//!
+//! let feature = |curie: &str| SimplePhenotypicFeature::new(
+//!     curie.parse().unwrap(),
+//!     Fraction::try_from((1u32, 1)).unwrap(),
+//! );
+//!
+//! let patient = vec![
+//!     // Congenital polydactyly.
+//!     feature("HP:0010442").with_onset(TimeElement::OntologyClass("HP:0003577".parse().unwrap())),
+//!     // Seizures from 6 months to 3 years of age.
+//!     feature("HP:0001250")
+//!         .with_onset(TimeElement::Age("P6M".parse().unwrap()))
+//!         .with_resolution(TimeElement::Age("P3Y".parse().unwrap())),
+//!     // Intellectual disability with unknown onset.
+//!     feature("HP:0001249"),
+//! ];
+//!
+//! let age: Age = "P1Y6M".parse().unwrap();
+//! assert_eq!(patient.features_present_at(&age).count(), 2);
+//!
+//! let age: Age = "P5Y".parse().unwrap();
+//! assert_eq!(patient.features_present_at(&age).count(), 1);
+//! assert_eq!(patient.features_with_onset_before(&age).count(), 2);
+//! ```
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use ontolius::TermId;
+
+use crate::{Observable, ObservableFeatures, PhenotypesError};
+
+/// The mean number of days in a year.
+const DAYS_PER_YEAR: f64 = 365.25;
+/// The mean number of days in a month.
+const DAYS_PER_MONTH: f64 = DAYS_PER_YEAR / 12.;
+/// The length of a term pregnancy in days.
+const TERM_PREGNANCY_DAYS: f64 = 280.;
+
+/// The day ranges of the HPO onset terms.
+static ONSET_TERMS: LazyLock<[(TermId, RangeInclusive<f64>); 13]> = LazyLock::new(|| {
+    let year = DAYS_PER_YEAR;
+    let onset = |id: &str, range| (TermId::from(("HP", id)), range);
+    [
+        // Antenatal onset
+        onset("0030674", -TERM_PREGNANCY_DAYS..=0.),
+        // Embryonal onset
+        onset("0011460", -TERM_PREGNANCY_DAYS..=-224.),
+        // Fetal onset
+        onset("0011461", -224. ..=0.),
+        // Congenital onset
+        onset("0003577", 0. ..=0.),
+        // Neonatal onset
+        onset("0003623", 0. ..=28.),
+        // Pediatric onset
+        onset("0410280", 0. ..=16. * year),
+        // Infantile onset
+        onset("0003593", 28. ..=year),
+        // Childhood onset
+        onset("0011463", year..=5. * year),
+        // Juvenile onset
+        onset("0003621", 5. * year..=16. * year),
+        // Adult onset
+        onset("0003581", 16. * year..=f64::INFINITY),
+        // Young adult onset
+        onset("0011462", 16. * year..=40. * year),
+        // Middle age onset
+        onset("0003596", 40. * year..=60. * year),
+        // Late onset
+        onset("0003584", 60. * year..=f64::INFINITY),
+    ]
+});
+
+/// An age represented as an ISO 8601 duration, such as `P1Y6M` for one and a half years.
+///
+/// Only the year, month, week, and day designators are supported.
+/// Each designator may be used at most once and in the `Y`, `M`, `W`, `D` order.
+///
+/// ```
+/// use phenotypes::temporal::Age;
+///
+/// let age: Age = "P1Y6M".parse().unwrap();
+/// assert_eq!(age.years(), 1);
+/// assert_eq!(age.months(), 6);
+/// assert_eq!(age.to_days(), 547.875);
+/// assert_eq!(age.to_string(), "P1Y6M");
+///
+/// assert!("1 year".parse::<Age>().is_err());
+/// assert!("PT12H".parse::<Age>().is_err());
+///
+/// // Repeated or out-of-order designators are rejected.
+/// assert!("P1Y1Y".parse::<Age>().is_err());
+/// assert!("P1M1Y".parse::<Age>().is_err());
+/// assert!("P2D1W".parse::<Age>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Age {
+    years: u32,
+    months: u32,
+    weeks: u32,
+    days: u32,
+}
+
+impl Age {
+    /// Create an age from its components.
+    pub fn new(years: u32, months: u32, weeks: u32, days: u32) -> Self {
+        Self {
+            years,
+            months,
+            weeks,
+            days,
+        }
+    }
+
+    /// Get the number of years.
+    pub fn years(&self) -> u32 {
+        self.years
+    }
+
+    /// Get the number of months.
+    pub fn months(&self) -> u32 {
+        self.months
+    }
+
+    /// Get the number of weeks.
+    pub fn weeks(&self) -> u32 {
+        self.weeks
+    }
+
+    /// Get the number of days.
+    pub fn days(&self) -> u32 {
+        self.days
+    }
+
+    /// Get the age in days, using the mean lengths of a year and a month.
+    pub fn to_days(&self) -> f64 {
+        f64::from(self.years) * DAYS_PER_YEAR
+            + f64::from(self.months) * DAYS_PER_MONTH
+            + f64::from(self.weeks) * 7.
+            + f64::from(self.days)
+    }
+}
+
+impl FromStr for Age {
+    type Err = PhenotypesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || PhenotypesError::InvalidValue {
+            value: s.to_string(),
+            expected: "an ISO 8601 duration such as P1Y6M".to_string(),
+        };
+        let body = s.strip_prefix('P').ok_or_else(error)?;
+        if body.is_empty() {
+            return Err(error());
+        }
+
+        let mut age = Age::default();
+        let mut number = String::new();
+        // The index of the first designator that may still follow.
+        let mut next = 0;
+        for c in body.chars() {
+            if c.is_ascii_digit() {
+                number.push(c);
+                continue;
+            }
+            let value: u32 = number.parse().map_err(|_| error())?;
+            number.clear();
+            let index = ['Y', 'M', 'W', 'D']
+                .iter()
+                .position(|&designator| designator == c)
+                .filter(|&index| index >= next)
+                .ok_or_else(error)?;
+            next = index + 1;
+            match index {
+                0 => age.years = value,
+                1 => age.months = value,
+                2 => age.weeks = value,
+                _ => age.days = value,
+            }
+        }
+        if number.is_empty() {
+            Ok(age)
+        } else {
+            Err(error())
+        }
+    }
+}
+
+/// Format the age as an ISO 8601 duration.
+impl Display for Age {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("P")?;
+        let parts = [
+            (self.years, 'Y'),
+            (self.months, 'M'),
+            (self.weeks, 'W'),
+            (self.days, 'D'),
+        ];
+        let mut written = false;
+        for (value, designator) in parts {
+            if value > 0 {
+                write!(f, "{value}{designator}")?;
+                written = true;
+            }
+        }
+        if !written {
+            f.write_str("0D")?;
+        }
+        Ok(())
+    }
+}
+
+/// A gestational age in completed weeks and days.
+///
+/// The gestational age is mapped to the negative days before the birth
+/// at the end of a term pregnancy of 40 weeks.
+///
+/// ```
+/// use phenotypes::temporal::GestationalAge;
+///
+/// let age = GestationalAge::new(20, 3);
+/// assert_eq!(age.to_days(), -137.);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GestationalAge {
+    weeks: u32,
+    days: u32,
+}
+
+impl GestationalAge {
+    /// Create a gestational age.
+    pub fn new(weeks: u32, days: u32) -> Self {
+        Self { weeks, days }
+    }
+
+    /// Get the completed weeks.
+    pub fn weeks(&self) -> u32 {
+        self.weeks
+    }
+
+    /// Get the days on top of the completed weeks.
+    pub fn days(&self) -> u32 {
+        self.days
+    }
+
+    /// Get the days relative to the birth.
+    pub fn to_days(&self) -> f64 {
+        f64::from(self.weeks) * 7. + f64::from(self.days) - TERM_PREGNANCY_DAYS
+    }
+}
+
+/// A point or a period in the life of an individual.
+///
+/// ```
+/// use phenotypes::temporal::{Age, GestationalAge, TimeElement};
+///
+/// let infantile = TimeElement::OntologyClass("HP:0003593".parse().unwrap());
+/// assert_eq!(infantile.day_range(), Some(28. ..=365.25));
+///
+/// let range = TimeElement::AgeRange {
+///     start: "P1Y".parse().unwrap(),
+///     end: "P2Y".parse().unwrap(),
+/// };
+/// assert_eq!(range.day_range(), Some(365.25..=730.5));
+///
+/// let prenatal = TimeElement::GestationalAge(GestationalAge::new(38, 0));
+/// assert_eq!(prenatal.day_range(), Some(-14. ..=-14.));
+///
+/// // Seizure is not an onset term.
+/// let seizure = TimeElement::OntologyClass("HP:0001250".parse().unwrap());
+/// assert_eq!(seizure.day_range(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeElement {
+    /// An age since birth.
+    Age(Age),
+    /// An age before birth.
+    GestationalAge(GestationalAge),
+    /// A term of the [Onset (HP:0003674)](https://hpo.jax.org/browse/term/HP:0003674)
+    /// HPO sub-module, such as [Congenital onset (HP:0003577)](https://hpo.jax.org/browse/term/HP:0003577).
+    OntologyClass(#[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))] TermId),
+    /// A period between the `start` and the `end` ages.
+    AgeRange { start: Age, end: Age },
+}
+
+impl TimeElement {
+    /// Get the range of the days since birth covered by the time element.
+    ///
+    /// A point in time yields a single-day range. The open-ended onset terms,
+    /// such as the adult onset, end at infinity.
+    ///
+    /// Returns `None` if the ontology class is not an HPO onset term.
+    pub fn day_range(&self) -> Option<RangeInclusive<f64>> {
+        match self {
+            TimeElement::Age(age) => Some(age.to_days()..=age.to_days()),
+            TimeElement::GestationalAge(age) => Some(age.to_days()..=age.to_days()),
+            TimeElement::OntologyClass(term_id) => ONSET_TERMS
+                .iter()
+                .find(|(onset, _)| onset == term_id)
+                .map(|(_, range)| range.clone()),
+            TimeElement::AgeRange { start, end } => Some(start.to_days()..=end.to_days()),
+        }
+    }
+}
+
+impl From<Age> for TimeElement {
+    fn from(value: Age) -> Self {
+        TimeElement::Age(value)
+    }
+}
+
+impl From<GestationalAge> for TimeElement {
+    fn from(value: GestationalAge) -> Self {
+        TimeElement::GestationalAge(value)
+    }
+}
+
+/// An [`Observable`] entity with a known onset and, possibly, resolution.
+///
+/// Both the onset and the resolution are unknown by default.
+pub trait TemporalObservable: Observable {
+    /// Get the time when the feature was first observed.
+    fn onset(&self) -> Option<&TimeElement> {
+        None
+    }
+
+    /// Get the time when the feature stopped being observed.
+    fn resolution(&self) -> Option<&TimeElement> {
+        None
+    }
+
+    /// Test if the feature was certainly present at the `age`.
+    ///
+    /// The feature must be present, its onset must end before or at the `age`,
+    /// and its resolution, if any, must start after the `age`.
+    /// A feature with an unknown onset is never certainly present.
+    fn is_present_at(&self, age: &Age) -> bool {
+        let days = age.to_days();
+        self.is_present()
+            && self
+                .onset()
+                .and_then(TimeElement::day_range)
+                .is_some_and(|onset| *onset.end() <= days)
+            && self
+                .resolution()
+                .and_then(TimeElement::day_range)
+                .is_none_or(|resolution| *resolution.start() > days)
+    }
+
+    /// Test if the onset of the feature certainly ended before the `age`.
+    fn has_onset_before(&self, age: &Age) -> bool {
+        self.onset()
+            .and_then(TimeElement::day_range)
+            .is_some_and(|onset| *onset.end() < age.to_days())
+    }
+}
+
+/// Extension of [`ObservableFeatures`] with [`TemporalObservable`] features
+/// for querying the features at a point in time.
+///
+/// The trait is implemented for all such containers.
+pub trait TemporalFeatures: ObservableFeatures
+where
+    Self::Feature: TemporalObservable,
+{
+    /// Get an iterator over the features that were certainly present at the `age`.
+    ///
+    /// See [`TemporalObservable::is_present_at`] for the details.
+    fn features_present_at<'a>(&'a self, age: &'a Age) -> impl Iterator<Item = &'a Self::Feature>
+    where
+        Self::Feature: 'a,
+    {
+        self.present_features()
+            .filter(move |feature| feature.is_present_at(age))
+    }
+
+    /// Get an iterator over the present features whose onset ended before the `age`.
+    ///
+    /// The features may have resolved since.
+    fn features_with_onset_before<'a>(
+        &'a self,
+        age: &'a Age,
+    ) -> impl Iterator<Item = &'a Self::Feature>
+    where
+        Self::Feature: 'a,
+    {
+        self.present_features()
+            .filter(move |feature| feature.has_onset_before(age))
+    }
+}
+
+impl<T> TemporalFeatures for T
+where
+    T: ObservableFeatures,
+    T::Feature: TemporalObservable,
+{
+}
@@ -0,0 +1,418 @@
+//! A module for detecting and resolving contradictory observations.
+//!
+//! Nothing prevents an [`ObservableFeatures`] container from listing a feature
+//! as both present and excluded, or a feature as present while its ancestor is excluded.
+//! Such conflicts break the downstream analyses and should be detected
+//! with [`find_conflicts`] or [`find_conflicts_with_hierarchy`],
+//! and either fixed by the curator or resolved by a [`ResolutionPolicy`].
+//!
+//! ## Examples
+//!
+//! ```
+//! # let hpo = include!("../tests/common/toy_hpo.rs");
+//! use ontolius::{Identified, TermId};
+//! use phenotypes::{Observable, ObservableFeatures, ObservationState};
+//! use phenotypes::validation::{
+//!     Conflict, ResolutionPolicy, find_conflicts_with_hierarchy, resolve_conflicts_with_hierarchy,
+//! };
+//!
+//! struct Observation(TermId, ObservationState);
+//!
+//! impl Identified for Observation {
+//!     fn identifier(&self) -> &TermId {
+//!         &self.0
+//!     }
+//! }
+//!
+//! impl Observable for Observation {
+//!     fn observation_state(&self) -> ObservationState {
+//!         self.1
+//!     }
+//! }
+//!
+//! let polydactyly: TermId = "HP:0010442".parse().unwrap();
+//! let postaxial: TermId = "HP:0100259".parse().unwrap();
+//! let seizure: TermId = "HP:0001250".parse().unwrap();
+//!
+//! let subject = vec![
+//!     Observation(postaxial.clone(), ObservationState::Present),
+//!     Observation(polydactyly.clone(), ObservationState::Excluded),
+//!     Observation(seizure.clone(), ObservationState::Present),
+//!     Observation(seizure.clone(), ObservationState::Excluded),
+//! ];
+//!
+//! // `hpo` is a small hand-built ontology with the HPO terms used in the example.
+//! let conflicts = find_conflicts_with_hierarchy(&subject, &hpo);
+//! assert_eq!(
+//!     conflicts,
+//!     vec![
+//!         Conflict::Contradiction { term_id: seizure.clone() },
+//!         Conflict::ExcludedAncestor { present: postaxial.clone(), excluded: polydactyly.clone() },
+//!     ],
+//! );
+//!
+//! let resolved = resolve_conflicts_with_hierarchy(&subject, &hpo, ResolutionPolicy::PreferPresent);
+//! let present: Vec<_> = resolved.present_features().map(|f| f.identifier()).collect();
+//! assert_eq!(present, [&postaxial, &seizure]);
+//! assert_eq!(resolved.excluded_feature_count(), 0);
+//! ```
+use std::collections::HashSet;
+
+use ontolius::ontology::HierarchyQueries;
+use ontolius::{Identified, TermId};
+
+use crate::{ObservableFeatures, ObservationState};
+
+/// A conflict between the observations of an item.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Conflict {
+    /// The term is listed more than once in the same `state`.
+    Duplicate {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
+        term_id: TermId,
+        state: ObservationState,
+    },
+    /// The term is listed as both present and excluded.
+    Contradiction {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
+        term_id: TermId,
+    },
+    /// The `present` term is a descendant of the `excluded` term.
+    ///
+    /// Due to the true path rule, the exclusion of the ancestor implies
+    /// the exclusion of the present term.
+    ExcludedAncestor {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
+        present: TermId,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
+        excluded: TermId,
+    },
+    /// The present `ancestor` is implied by the present `descendant`
+    /// and its annotation is redundant.
+    RedundantAncestor {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
+        ancestor: TermId,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_curie"))]
+        descendant: TermId,
+    },
+}
+
+/// The policy for resolving the conflicts.
+///
+/// The policy decides the contradictions and the excluded ancestors
+/// of the present terms:
+///
+/// * [`Conflict::Contradiction`] - keep the term in the preferred state,
+///   or drop the term altogether with [`ResolutionPolicy::DropConflicting`].
+/// * [`Conflict::ExcludedAncestor`] - keep the more specific present term
+///   with [`ResolutionPolicy::PreferPresent`], keep the excluded ancestor
+///   with [`ResolutionPolicy::PreferExcluded`], or drop both terms.
+///
+/// Regardless of the policy, only the first of the [`Conflict::Duplicate`] features is kept
+/// and the [`Conflict::RedundantAncestor`] is dropped, unless the policy dropped
+/// all its present descendants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResolutionPolicy {
+    /// Prefer the present features.
+    PreferPresent,
+    /// Prefer the excluded features.
+    PreferExcluded,
+    /// Drop all features involved in a conflict.
+    DropConflicting,
+}
+
+/// Find the conflicts that do not require the ontology hierarchy,
+/// i.e. the duplicates and the contradictions.
+///
+/// ```
+/// use ontolius::TermId;
+/// use phenotypes::{Fraction, ObservationState};
+/// use phenotypes::simple::SimplePhenotypicFeature;
+/// use phenotypes::validation::{Conflict, find_conflicts};
+///
+/// let seizure: TermId = "HP:0001250".parse().unwrap();
+/// let present = Fraction::try_from((1, 1)).unwrap();
+/// let features = vec![
+///     SimplePhenotypicFeature::new(seizure.clone(), present.clone()),
+///     SimplePhenotypicFeature::new(seizure.clone(), present),
+/// ];
+///
+/// assert_eq!(
+///     find_conflicts(&features),
+///     vec![Conflict::Duplicate { term_id: seizure, state: ObservationState::Present }],
+/// );
+/// ```
+pub fn find_conflicts<C>(features: &C) -> Vec<Conflict>
+where
+    C: ObservableFeatures,
+    C::Feature: Identified,
+{
+    Conflicts::new(features, None::<&NoHierarchy>).conflicts
+}
+
+/// Find all conflicts, including the conflicts implied by the ontology `hierarchy`.
+pub fn find_conflicts_with_hierarchy<C, O>(features: &C, hierarchy: &O) -> Vec<Conflict>
+where
+    C: ObservableFeatures,
+    C::Feature: Identified,
+    O: HierarchyQueries,
+{
+    Conflicts::new(features, Some(hierarchy)).conflicts
+}
+
+/// Resolve the conflicts found by [`find_conflicts`] using the `policy`.
+pub fn resolve_conflicts<C>(
+    features: &C,
+    policy: ResolutionPolicy,
+) -> ResolvedFeatures<'_, C::Feature>
+where
+    C: ObservableFeatures,
+    C::Feature: Identified,
+{
+    Conflicts::new(features, None::<&NoHierarchy>).resolve(features, policy)
+}
+
+/// Resolve the conflicts found by [`find_conflicts_with_hierarchy`] using the `policy`.
+///
+/// A present ancestor is kept if the policy drops its present descendants:
+///
+/// ```
+/// # let hpo = include!("../tests/common/toy_hpo.rs");
+/// use ontolius::{Identified, TermId};
+/// use phenotypes::ObservableFeatures;
+/// use phenotypes::simple::SimpleObservedFeature;
+/// use phenotypes::validation::{ResolutionPolicy, resolve_conflicts_with_hierarchy};
+///
+/// let polydactyly: TermId = "HP:0010442".parse().unwrap();
+/// let postaxial: TermId = "HP:0100259".parse().unwrap();
+///
+/// let subject = vec![
+///     SimpleObservedFeature::present(polydactyly.clone()),
+///     SimpleObservedFeature::present(postaxial.clone()),
+///     SimpleObservedFeature::excluded(postaxial.clone()),
+/// ];
+///
+/// for policy in [ResolutionPolicy::PreferExcluded, ResolutionPolicy::DropConflicting] {
+///     let resolved = resolve_conflicts_with_hierarchy(&subject, &hpo, policy);
+///     let present: Vec<_> = resolved.present_features().map(|f| f.identifier()).collect();
+///     assert_eq!(present, [&polydactyly]);
+/// }
+///
+/// // The ancestor is redundant if the descendant is kept.
+/// let resolved = resolve_conflicts_with_hierarchy(&subject, &hpo, ResolutionPolicy::PreferPresent);
+/// let present: Vec<_> = resolved.present_features().map(|f| f.identifier()).collect();
+/// assert_eq!(present, [&postaxial]);
+/// ```
+pub fn resolve_conflicts_with_hierarchy<'a, C, O>(
+    features: &'a C,
+    hierarchy: &O,
+    policy: ResolutionPolicy,
+) -> ResolvedFeatures<'a, C::Feature>
+where
+    C: ObservableFeatures,
+    C::Feature: Identified,
+    O: HierarchyQueries,
+{
+    Conflicts::new(features, Some(hierarchy)).resolve(features, policy)
+}
+
+/// The features that remain after resolving the conflicts.
+///
+/// The features are [`ObservableFeatures`] in their original order,
+/// except for the unknown features which are not retained.
+#[derive(Debug, Clone)]
+pub struct ResolvedFeatures<'a, F> {
+    present: Vec<&'a F>,
+    excluded: Vec<&'a F>,
+}
+
+impl<F> ObservableFeatures for ResolvedFeatures<'_, F> {
+    type Feature = F;
+
+    fn present_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        self.present.iter().copied()
+    }
+
+    fn excluded_features(&self) -> impl Iterator<Item = &Self::Feature> {
+        self.excluded.iter().copied()
+    }
+}
+
+/// A hierarchy for the cases where no ontology is available.
+struct NoHierarchy;
+
+impl HierarchyQueries for NoHierarchy {
+    fn is_child_of<S, O>(&self, _: &S, _: &O) -> bool {
+        false
+    }
+
+    fn is_descendant_of<S, O>(&self, _: &S, _: &O) -> bool {
+        false
+    }
+
+    fn is_parent_of<S, O>(&self, _: &S, _: &O) -> bool {
+        false
+    }
+
+    fn is_ancestor_of<S, O>(&self, _: &S, _: &O) -> bool {
+        false
+    }
+}
+
+struct Conflicts {
+    conflicts: Vec<Conflict>,
+}
+
+impl Conflicts {
+    fn new<C, O>(features: &C, hierarchy: Option<&O>) -> Self
+    where
+        C: ObservableFeatures,
+        C::Feature: Identified,
+        O: HierarchyQueries,
+    {
+        let mut conflicts = vec![];
+        let present = unique_term_ids(
+            features.present_features(),
+            ObservationState::Present,
+            &mut conflicts,
+        );
+        let excluded = unique_term_ids(
+            features.excluded_features(),
+            ObservationState::Excluded,
+            &mut conflicts,
+        );
+
+        for &term_id in &present {
+            if excluded.contains(&term_id) {
+                conflicts.push(Conflict::Contradiction {
+                    term_id: term_id.clone(),
+                });
+            }
+        }
+
+        if let Some(hierarchy) = hierarchy {
+            for &p in &present {
+                for &e in &excluded {
+                    if hierarchy.is_ancestor_of(e, p) {
+                        conflicts.push(Conflict::ExcludedAncestor {
+                            present: p.clone(),
+                            excluded: e.clone(),
+                        });
+                    }
+                }
+            }
+            for &ancestor in &present {
+                for &descendant in &present {
+                    if hierarchy.is_ancestor_of(ancestor, descendant) {
+                        conflicts.push(Conflict::RedundantAncestor {
+                            ancestor: ancestor.clone(),
+                            descendant: descendant.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Self { conflicts }
+    }
+
+    fn resolve<'a, C>(
+        &self,
+        features: &'a C,
+        policy: ResolutionPolicy,
+    ) -> ResolvedFeatures<'a, C::Feature>
+    where
+        C: ObservableFeatures,
+        C::Feature: Identified,
+    {
+        let mut drop_present = HashSet::new();
+        let mut drop_excluded = HashSet::new();
+        for conflict in &self.conflicts {
+            match conflict {
+                Conflict::Duplicate { .. } => {}
+                Conflict::Contradiction { term_id } => match policy {
+                    ResolutionPolicy::PreferPresent => {
+                        drop_excluded.insert(term_id);
+                    }
+                    ResolutionPolicy::PreferExcluded => {
+                        drop_present.insert(term_id);
+                    }
+                    ResolutionPolicy::DropConflicting => {
+                        drop_present.insert(term_id);
+                        drop_excluded.insert(term_id);
+                    }
+                },
+                Conflict::ExcludedAncestor { present, excluded } => match policy {
+                    ResolutionPolicy::PreferPresent => {
+                        drop_excluded.insert(excluded);
+                    }
+                    ResolutionPolicy::PreferExcluded => {
+                        drop_present.insert(present);
+                    }
+                    ResolutionPolicy::DropConflicting => {
+                        drop_present.insert(present);
+                        drop_excluded.insert(excluded);
+                    }
+                },
+                Conflict::RedundantAncestor { .. } => {}
+            }
+        }
+
+        // The ancestor is redundant only if its descendant survived the resolution.
+        let mut redundant = HashSet::new();
+        for conflict in &self.conflicts {
+            if let Conflict::RedundantAncestor {
+                ancestor,
+                descendant,
+            } = conflict
+                && !drop_present.contains(descendant)
+            {
+                redundant.insert(ancestor);
+            }
+        }
+        drop_present.extend(redundant);
+
+        ResolvedFeatures {
+            present: retain(features.present_features(), &drop_present),
+            excluded: retain(features.excluded_features(), &drop_excluded),
+        }
+    }
+}
+
+/// Get the unique term IDs of the `features` and report the duplicates.
+fn unique_term_ids<'a, F>(
+    features: impl Iterator<Item = &'a F>,
+    state: ObservationState,
+    conflicts: &mut Vec<Conflict>,
+) -> Vec<&'a TermId>
+where
+    F: Identified + 'a,
+{
+    let mut seen = HashSet::new();
+    let mut unique = vec![];
+    for feature in features {
+        let term_id = feature.identifier();
+        if seen.insert(term_id) {
+            unique.push(term_id);
+        } else {
+            conflicts.push(Conflict::Duplicate {
+                term_id: term_id.clone(),
+                state,
+            });
+        }
+    }
+    unique
+}
+
+/// Keep the first occurrence of the features that are not dropped.
+fn retain<'a, F>(features: impl Iterator<Item = &'a F>, dropped: &HashSet<&TermId>) -> Vec<&'a F>
+where
+    F: Identified + 'a,
+{
+    let mut seen = HashSet::new();
+    features
+        .filter(|f| !dropped.contains(f.identifier()) && seen.insert(f.identifier()))
+        .collect()
+}
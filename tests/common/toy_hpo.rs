@@ -0,0 +1,42 @@
+// A small hand-built HPO with the terms used in the doctests:
+//
+// All (HP:0000001)
+// └── Phenotypic abnormality (HP:0000118)
+//     ├── Abnormality of limbs (HP:0040064)
+//     │   └── Polydactyly (HP:0010442)
+//     │       ├── Postaxial polydactyly (HP:0100259)
+//     │       └── Preaxial polydactyly (HP:0100258)
+//     └── Abnormality of the nervous system (HP:0000707)
+//         └── Seizure (HP:0001250)
+//
+// The file is an expression that evaluates to the ontology:
+//
+// let hpo = include!("../tests/common/toy_hpo.rs");
+{
+    use std::collections::HashMap;
+
+    use ontolius::io::{GraphEdge, OntologyData, Relationship};
+    use ontolius::ontology::csr::MinimalCsrOntology;
+    use ontolius::term::simple::SimpleMinimalTerm;
+
+    let terms = [
+        ("HP:0000001", "All"),
+        ("HP:0000118", "Phenotypic abnormality"),
+        ("HP:0040064", "Abnormality of limbs"),
+        ("HP:0010442", "Polydactyly"),
+        ("HP:0100259", "Postaxial polydactyly"),
+        ("HP:0100258", "Preaxial polydactyly"),
+        ("HP:0000707", "Abnormality of the nervous system"),
+        ("HP:0001250", "Seizure"),
+    ];
+    let terms: Vec<_> = terms
+        .iter()
+        .map(|(curie, name)| SimpleMinimalTerm::new(curie.parse().unwrap(), *name, vec![], false))
+        .collect();
+    let edges: Vec<GraphEdge<u32>> = [(1, 0), (2, 1), (3, 2), (4, 3), (5, 3), (6, 1), (7, 6)]
+        .into_iter()
+        .map(|(sub, obj)| GraphEdge::from((sub, Relationship::Child, obj)))
+        .collect();
+
+    MinimalCsrOntology::try_from(OntologyData::from((terms, edges, HashMap::new()))).unwrap()
+}